@@ -0,0 +1,11 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use nvme_pel::pel::parse_ms;
+
+fuzz_target!(|data: [u8; 6]| {
+    let (remainder, ms) = parse_ms(&data).unwrap();
+    assert!(remainder.is_empty());
+    // only 6 bytes are used, so the top 2 bytes of the u64 must be zero
+    assert!(ms <= 0x0000_ffff_ffff_ffff);
+});
@@ -0,0 +1,190 @@
+// Reads the persistent event log from an NVMe character device with the admin passthrough ioctl
+
+use std::{fs::File, os::fd::AsRawFd, path::Path};
+
+use crate::pel::{parse_pel, Pel, PelError, MINIMUM_LOG_SIZE, PEL_LOG_ID};
+
+// _IOWR('N', 0x41, struct nvme_passthru_cmd)
+const NVME_IOCTL_ADMIN_CMD: u32 = 0xc048_4e41;
+const GET_LOG_PAGE: u8 = 0x02;
+const NSID_ALL: u32 = 0xffff_ffff;
+// Log specific field actions for the persistent event log
+const ESTABLISH_CONTEXT_AND_READ: u32 = 0x1;
+const READ: u32 = 0x0;
+const RELEASE_CONTEXT: u32 = 0x2;
+// Kept well under the maximum data transfer size of most controllers
+const MAX_CHUNK_LEN: usize = 64 * 1024;
+// Far beyond any real log, so a corrupt header can't make us allocate gigabytes
+const MAX_LOG_LEN: u64 = 256 * 1024 * 1024;
+// The largest event: a 255 + 3 byte header and 65535 bytes of event data
+const MAX_EVENT_LEN: u64 = 258 + u16::MAX as u64;
+
+// struct nvme_passthru_cmd from linux/nvme_ioctl.h
+#[repr(C)]
+#[derive(Default)]
+struct PassthruCmd {
+    opcode: u8,
+    flags: u8,
+    rsvd1: u16,
+    nsid: u32,
+    cdw2: u32,
+    cdw3: u32,
+    metadata: u64,
+    addr: u64,
+    metadata_len: u32,
+    data_len: u32,
+    cdw10: u32,
+    cdw11: u32,
+    cdw12: u32,
+    cdw13: u32,
+    cdw14: u32,
+    cdw15: u32,
+    timeout_ms: u32,
+    result: u32,
+}
+
+/// Reads the persistent event log from an NVMe controller such as `/dev/nvme0`.
+///
+/// ```no_run
+/// use nvme_pel::device::PelReader;
+///
+/// let reader = PelReader::open("/dev/nvme0")?;
+/// let pel = reader.read_pel()?;
+/// println!("{pel}");
+/// # Ok::<(), nvme_pel::pel::PelError>(())
+/// ```
+pub struct PelReader {
+    file: File,
+}
+
+impl PelReader {
+    pub fn new(file: File) -> Self {
+        Self { file }
+    }
+
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, PelError> {
+        Ok(Self::new(File::open(path)?))
+    }
+
+    /// Reads the header to find the log length, then the whole log.
+    pub fn read_pel(&self) -> Result<Pel, PelError> {
+        let mut log = vec![0u8; MINIMUM_LOG_SIZE as usize];
+        self.get_log_page(ESTABLISH_CONTEXT_AND_READ, 0, &mut log)?;
+        let result = self.read_events(&mut log);
+        // the context stays open on the controller until it's released
+        let release = self.get_log_page(RELEASE_CONTEXT, 0, &mut [0u8; MINIMUM_LOG_SIZE as usize]);
+        result?;
+        release?;
+        parse_pel(&log)
+    }
+
+    fn read_events(&self, log: &mut Vec<u8>) -> Result<(), PelError> {
+        if log[0] != PEL_LOG_ID {
+            return Err(PelError::InvalidLogId {
+                expected: PEL_LOG_ID,
+                got: log[0],
+            });
+        }
+        let len = log_len(log)?;
+        let mut offset = log.len();
+        log.resize(len.max(offset), 0);
+        while offset < log.len() {
+            let end = (offset + MAX_CHUNK_LEN).min(log.len());
+            self.get_log_page(READ, offset as u64, &mut log[offset..end])?;
+            offset = end;
+        }
+        Ok(())
+    }
+
+    fn get_log_page(&self, action: u32, offset: u64, buf: &mut [u8]) -> Result<(), PelError> {
+        // 0's based number of dwords
+        let numd = (buf.len() / 4).saturating_sub(1) as u32;
+        let mut cmd = PassthruCmd {
+            opcode: GET_LOG_PAGE,
+            nsid: NSID_ALL,
+            addr: buf.as_mut_ptr() as u64,
+            data_len: buf.len() as u32,
+            // 31:16 - NUMDL, 11:08 - LSP, 07:00 - LID
+            cdw10: (numd & 0xffff) << 16 | action << 8 | PEL_LOG_ID as u32,
+            // 15:00 - NUMDU
+            cdw11: numd >> 16,
+            cdw12: offset as u32,
+            cdw13: (offset >> 32) as u32,
+            ..Default::default()
+        };
+
+        // SAFETY: cmd matches the kernel's struct nvme_passthru_cmd and addr points to data_len
+        // bytes that live for the duration of the call
+        let ret = unsafe {
+            libc::ioctl(
+                self.file.as_raw_fd(),
+                NVME_IOCTL_ADMIN_CMD as _,
+                &mut cmd as *mut PassthruCmd,
+            )
+        };
+        match ret {
+            0 => Ok(()),
+            ret if ret < 0 => Err(std::io::Error::last_os_error().into()),
+            status => Err(PelError::AdminCommandFailed {
+                status: status as u16,
+            }),
+        }
+    }
+}
+
+// Checks the total log length against the rest of the header before it's used to size the buffer
+fn log_len(header: &[u8]) -> Result<usize, PelError> {
+    // 07:04 - total number of events
+    let num_events = u32::from_le_bytes(header[4..8].try_into().unwrap());
+    // 15:08 - total log length
+    let len = u64::from_le_bytes(header[8..16].try_into().unwrap());
+    // 19:18 - log header length
+    let header_len = u16::from_le_bytes(header[18..20].try_into().unwrap());
+
+    let max_len = (header_len as u64).max(MINIMUM_LOG_SIZE) + num_events as u64 * MAX_EVENT_LEN;
+    // the log is read in dwords, anything after the last whole one would be dropped
+    if len > max_len.min(MAX_LOG_LEN) || len % 4 != 0 {
+        return Err(PelError::InvalidLogLength { len });
+    }
+    Ok(len as usize)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_passthru_cmd_size() {
+        // the size is encoded in the ioctl number
+        assert_eq!(
+            std::mem::size_of::<PassthruCmd>() as u32,
+            NVME_IOCTL_ADMIN_CMD >> 16 & 0x3fff
+        );
+    }
+
+    #[test]
+    fn test_log_len() {
+        let mut header = [0u8; MINIMUM_LOG_SIZE as usize];
+        header[4..8].copy_from_slice(&2u32.to_le_bytes());
+        header[8..16].copy_from_slice(&4096u64.to_le_bytes());
+        header[18..20].copy_from_slice(&512u16.to_le_bytes());
+        assert_eq!(log_len(&header), Ok(4096));
+
+        // not a whole number of dwords
+        header[8..16].copy_from_slice(&4098u64.to_le_bytes());
+        assert_eq!(
+            log_len(&header),
+            Err(PelError::InvalidLogLength { len: 4098 })
+        );
+
+        // more than two events can take up
+        let len = 512 + 2 * MAX_EVENT_LEN + 4;
+        header[8..16].copy_from_slice(&len.to_le_bytes());
+        assert_eq!(log_len(&header), Err(PelError::InvalidLogLength { len }));
+
+        // TNEV allows it, but it's still too big to allocate
+        header[4..8].copy_from_slice(&u32::MAX.to_le_bytes());
+        header[8..16].copy_from_slice(&(MAX_LOG_LEN + 4).to_le_bytes());
+        assert!(log_len(&header).is_err());
+    }
+}
@@ -0,0 +1,3 @@
+#[cfg(all(feature = "linux-ioctl", target_os = "linux"))]
+pub mod device;
+pub mod pel;
@@ -1,8 +0,0 @@
-#![allow(unused)]
-#![allow(dead_code)]
-
-mod pel;
-
-fn main() {
-    println!("Hello, world!");
-}
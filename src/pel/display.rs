@@ -0,0 +1,178 @@
+use std::fmt;
+
+use super::*;
+
+impl fmt::Display for Timestamp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let secs = self.ms.as_secs();
+        write!(
+            f,
+            "{}d {}h {}m {}.{:03}s [{:?}, {:?}]",
+            secs / 86_400,
+            secs / 3600 % 24,
+            secs / 60 % 60,
+            secs % 60,
+            self.ms.subsec_millis(),
+            self.origin,
+            self.synch
+        )
+    }
+}
+
+impl fmt::Display for Event {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.event_type() {
+            Some(event_type) => write!(f, "{event_type:?}")?,
+            None => write!(f, "Unknown")?,
+        }
+        match self {
+            Event::SmartHealth(record) => write!(
+                f,
+                ": composite temperature {}K, critical warning {:#04x}, {}% used",
+                record.info.composite_temperature,
+                record.info.critical_warning.0,
+                record.info.percentage_used
+            ),
+            Event::FwCommit(record) => write!(
+                f,
+                ": {} -> {}, slot {}, action {}",
+                record.info.previous_revision,
+                record.info.new_revision,
+                record.info.slot_number,
+                record.info.commit_action
+            ),
+            Event::FormatNvmComplete(record) => write!(f, ": nsid {}", record.info.nsid),
+            Event::SetFeature(record) => write!(
+                f,
+                ": fid {:#04x}{}",
+                record.info.feature_id(),
+                if record.info.save { ", saved" } else { "" }
+            ),
+            Event::ThermalExcursion(record) => write!(
+                f,
+                ": {} threshold {}K",
+                if record.info.over_threshold {
+                    "over"
+                } else {
+                    "under"
+                },
+                record.info.threshold_kelvin
+            ),
+            Event::VendorSpecifc(record) => write!(f, ": vendor {:#06x}", record.info.vendor_id),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl fmt::Display for Pel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Persistent Event Log (revision {})", self.revision)?;
+        writeln!(f, "  Serial number: {}", self.serial_num)?;
+        writeln!(f, "  Model number:  {}", self.model_num)?;
+        writeln!(
+            f,
+            "  VID / SSVID:   {:#06x} / {:#06x}",
+            self.vid, self.ssvid
+        )?;
+        write!(f, "  Events:        {}", self.num_events)?;
+        for (i, event) in self.events.iter().flatten().enumerate() {
+            write!(f, "\n  {i:>4} {} {event}", event.timestamp())?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pel::test_helpers::record;
+
+    #[test]
+    fn test_display_timestamp() {
+        let timestamp = Timestamp {
+            ms: Duration::from_millis(((2 * 24 + 3) * 60 + 45) * 60_000 + 12_345),
+            origin: TimestampOrigin::SetFeature,
+            synch: TimestampSynch::Continuous,
+        };
+        assert_eq!(
+            timestamp.to_string(),
+            "2d 3h 45m 12.345s [SetFeature, Continuous]"
+        );
+        assert_eq!(
+            Timestamp::default().to_string(),
+            "0d 0h 0m 0.000s [Reset, Continuous]"
+        );
+    }
+
+    #[test]
+    fn test_display_event() {
+        let event = Event::SmartHealth(record(
+            0,
+            SmartHealthInfo {
+                composite_temperature: 310,
+                critical_warning: CriticalWarning(0x02),
+                percentage_used: 4,
+                ..Default::default()
+            },
+        ));
+        assert_eq!(
+            event.to_string(),
+            "SmartHealth: composite temperature 310K, critical warning 0x02, 4% used"
+        );
+
+        let event = Event::FwCommit(record(
+            0,
+            FwCommitInfo {
+                previous_revision: "1.0".to_string(),
+                new_revision: "2.0".to_string(),
+                commit_action: 3,
+                slot_number: 1,
+                ..Default::default()
+            },
+        ));
+        assert_eq!(event.to_string(), "FwCommit: 1.0 -> 2.0, slot 1, action 3");
+
+        let event = Event::SetFeature(record(
+            0,
+            SetFeatureInfo {
+                feature: Feature::VolatileWriteCache,
+                save: true,
+                command_dwords: 1,
+                data: vec![],
+            },
+        ));
+        assert_eq!(event.to_string(), "SetFeature: fid 0x06, saved");
+
+        assert_eq!(Event::Por(record(0, PorInfo::default())).to_string(), "Por");
+        assert_eq!(
+            Event::Unknown(record(0, UnknownInfo::default())).to_string(),
+            "Unknown"
+        );
+    }
+
+    #[test]
+    fn test_display_pel() {
+        let mut pel: Pel = [
+            Event::Por(record(1000, PorInfo::default())),
+            Event::FormatNvmComplete(record(61_000, FormatNvmCompleteInfo { nsid: 1 })),
+        ]
+        .into_iter()
+        .collect();
+        pel.revision = 2;
+        pel.serial_num = "SN12345".to_string();
+        pel.model_num = "MODEL-01".to_string();
+        pel.vid = 0x144d;
+        pel.ssvid = 0x1028;
+
+        assert_eq!(
+            pel.to_string(),
+            "Persistent Event Log (revision 2)
+  Serial number: SN12345
+  Model number:  MODEL-01
+  VID / SSVID:   0x144d / 0x1028
+  Events:        2
+     0 0d 0h 0m 1.000s [Reset, Continuous] Por
+     1 0d 0h 1m 1.000s [Reset, Continuous] FormatNvmComplete: nsid 1"
+        );
+    }
+}
@@ -0,0 +1,217 @@
+use std::fmt;
+
+use nom::error::ErrorKind;
+
+// The log identifier for the persistent event log
+pub const PEL_LOG_ID: u8 = 0x0d;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum PelError {
+    // The input ended this many bytes early
+    InsufficientData {
+        needed: usize,
+    },
+    InvalidLogId {
+        expected: u8,
+        got: u8,
+    },
+    // The event header declares more data than is left in the input. `index` is the event's
+    // position in the log and `offset` where its header starts
+    EventBodyTooShort {
+        index: u32,
+        offset: usize,
+        event_type: u8,
+        declared_len: u16,
+        available: usize,
+    },
+    // The vendor specific information length (VSIL) is longer than the event length (EL)
+    VendorInfoTooLong {
+        index: u32,
+        offset: usize,
+        vendor_info_len: u16,
+        event_len: u16,
+    },
+    // The event data couldn't be parsed
+    InvalidEventData {
+        index: u32,
+        offset: usize,
+        event_type: u8,
+        kind: ErrorKind,
+    },
+    NomError(ErrorKind),
+    // A field is too long for its place in the binary log
+    FieldTooLong {
+        field: &'static str,
+        len: usize,
+        max: usize,
+    },
+    // The input to `Pel::from_hex_string` isn't valid hex
+    InvalidHexInput,
+    // The total log length in the header can't be right for the number of events, or isn't a
+    // whole number of dwords
+    InvalidLogLength {
+        len: u64,
+    },
+    // Reading the log from a device failed. The OS error is kept since most ioctl failures map
+    // to `ErrorKind::Other`
+    Io {
+        kind: std::io::ErrorKind,
+        raw_os_error: Option<i32>,
+    },
+    // The device completed the admin command with this status
+    AdminCommandFailed {
+        status: u16,
+    },
+}
+
+impl fmt::Display for PelError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PelError::InsufficientData { needed } => {
+                write!(f, "input is {needed} bytes too short")
+            }
+            PelError::InvalidLogId { expected, got } => {
+                write!(f, "invalid log id {got:#04x}, expected {expected:#04x}")
+            }
+            PelError::EventBodyTooShort {
+                index,
+                offset,
+                event_type,
+                declared_len,
+                available,
+            } => write!(
+                f,
+                "event {index} at offset {offset:#x}: event type {event_type:#04x} declares {declared_len} bytes but only {available} are available"
+            ),
+            PelError::VendorInfoTooLong {
+                index,
+                offset,
+                vendor_info_len,
+                event_len,
+            } => write!(
+                f,
+                "event {index} at offset {offset:#x}: vendor specific information length {vendor_info_len} is longer than the event length {event_len}"
+            ),
+            PelError::InvalidEventData {
+                index,
+                offset,
+                event_type,
+                kind,
+            } => write!(
+                f,
+                "event {index} at offset {offset:#x}: invalid data for event type {event_type:#04x}: {}",
+                kind.description()
+            ),
+            PelError::NomError(kind) => write!(f, "parse error: {}", kind.description()),
+            PelError::FieldTooLong { field, len, max } => {
+                write!(f, "{field} is {len} bytes long, at most {max} fit")
+            }
+            PelError::InvalidHexInput => write!(f, "invalid hex input"),
+            PelError::InvalidLogLength { len } => write!(f, "invalid total log length {len}"),
+            PelError::Io {
+                kind,
+                raw_os_error: Some(code),
+            } => write!(f, "I/O error: {kind} (os error {code})"),
+            PelError::Io { kind, .. } => write!(f, "I/O error: {kind}"),
+            PelError::AdminCommandFailed { status } => {
+                write!(f, "admin command failed with status {status:#06x}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for PelError {}
+
+impl From<std::io::Error> for PelError {
+    fn from(err: std::io::Error) -> Self {
+        PelError::Io {
+            kind: err.kind(),
+            raw_os_error: err.raw_os_error(),
+        }
+    }
+}
+
+impl<I> From<nom::Err<nom::error::Error<I>>> for PelError {
+    fn from(err: nom::Err<nom::error::Error<I>>) -> Self {
+        match err {
+            nom::Err::Incomplete(nom::Needed::Size(needed)) => PelError::InsufficientData {
+                needed: needed.get(),
+            },
+            nom::Err::Incomplete(nom::Needed::Unknown) => PelError::NomError(ErrorKind::Complete),
+            nom::Err::Error(err) | nom::Err::Failure(err) => PelError::NomError(err.code),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_nom_error() {
+        let err: nom::Err<nom::error::Error<&[u8]>> =
+            nom::Err::Error(nom::error::Error::new(&[], ErrorKind::Verify));
+        assert_eq!(PelError::from(err), PelError::NomError(ErrorKind::Verify));
+
+        let err: nom::Err<nom::error::Error<&[u8]>> = nom::Err::Incomplete(nom::Needed::new(4));
+        assert_eq!(
+            PelError::from(err),
+            PelError::InsufficientData { needed: 4 }
+        );
+    }
+
+    #[test]
+    fn test_from_io_error() {
+        let err = std::io::Error::from_raw_os_error(2);
+        let kind = err.kind();
+        assert_eq!(
+            PelError::from(err),
+            PelError::Io {
+                kind,
+                raw_os_error: Some(2)
+            }
+        );
+
+        let err = std::io::Error::from(std::io::ErrorKind::UnexpectedEof);
+        assert_eq!(
+            PelError::from(err),
+            PelError::Io {
+                kind: std::io::ErrorKind::UnexpectedEof,
+                raw_os_error: None
+            }
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            PelError::InvalidLogId {
+                expected: 0x0d,
+                got: 0x02
+            }
+            .to_string(),
+            "invalid log id 0x02, expected 0x0d"
+        );
+        assert_eq!(
+            PelError::EventBodyTooShort {
+                index: 2,
+                offset: 0x400,
+                event_type: 0x01,
+                declared_len: 536,
+                available: 100
+            }
+            .to_string(),
+            "event 2 at offset 0x400: event type 0x01 declares 536 bytes but only 100 are available"
+        );
+        assert_eq!(
+            PelError::VendorInfoTooLong {
+                index: 0,
+                offset: 0x200,
+                vendor_info_len: 8,
+                event_len: 4
+            }
+            .to_string(),
+            "event 0 at offset 0x200: vendor specific information length 8 is longer than the event length 4"
+        );
+    }
+}
@@ -1,32 +1,509 @@
+use std::time::Duration;
+
+// The SMART / health information log page
+#[derive(Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SmartHealthInfo {
+    pub critical_warning: CriticalWarning,
+    pub composite_temperature: u16,
+    pub available_spare: u8,
+    pub available_spare_threshold: u8,
+    pub percentage_used: u8,
+    pub endurance_group_critical_warning: u8,
+    pub data_units_read: u128,
+    pub data_units_written: u128,
+    pub host_read_commands: u128,
+    pub host_write_commands: u128,
+    pub io_controller_busy_time_minutes: u128,
+    pub power_cycles: u128,
+    pub power_on_hours: u128,
+    pub unsafe_shutdowns: u128,
+    pub media_errors: u128,
+    pub num_err_log_entries: u128,
+    pub warning_temp_time: u32,
+    pub critical_comp_time: u32,
+    pub temp_sensors: [u16; 8],
+    pub thermal_management_temp1_transition_count: u32,
+    pub thermal_management_temp2_transition_count: u32,
+    // WCTEMP and CCTEMP come from Identify Controller rather than the SMART / health log, so
+    // these are only set when the drive provides them elsewhere
+    pub warning_temperature_threshold_kelvin: Option<u16>,
+    pub critical_temperature_threshold_kelvin: Option<u16>,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CriticalWarning(pub u8);
+
+impl CriticalWarning {
+    pub fn available_spare_below_threshold(&self) -> bool {
+        self.0 & (1 << 0) != 0
+    }
+
+    pub fn temperature_threshold_exceeded(&self) -> bool {
+        self.0 & (1 << 1) != 0
+    }
+
+    pub fn reliability_degraded(&self) -> bool {
+        self.0 & (1 << 2) != 0
+    }
+
+    pub fn read_only(&self) -> bool {
+        self.0 & (1 << 3) != 0
+    }
+
+    pub fn volatile_memory_backup_failed(&self) -> bool {
+        self.0 & (1 << 4) != 0
+    }
+
+    pub fn persistent_memory_read_only(&self) -> bool {
+        self.0 & (1 << 5) != 0
+    }
+
+    pub fn any(&self) -> bool {
+        self.0 & 0x3f != 0
+    }
+}
+
+// Converts minutes to a `u64`, saturating at `u64::MAX` before multiplying
+fn minutes_saturating_mul(minutes: u128, rhs: u64) -> u64 {
+    u64::try_from(minutes)
+        .unwrap_or(u64::MAX)
+        .saturating_mul(rhs)
+}
+
+impl SmartHealthInfo {
+    /// Percentage points of available spare consumed per 1000 power-on hours since `prev`.
+    ///
+    /// Returns `None` if no time has elapsed or if the spare increased. A rate above 5% per 1000
+    /// hours is typically concerning.
+    pub fn spare_depletion_rate_per_1000_hours(
+        &self,
+        prev: &SmartHealthInfo,
+        elapsed_ms: u64,
+    ) -> Option<f64> {
+        if elapsed_ms == 0 {
+            return None;
+        }
+        let depleted = prev.available_spare.checked_sub(self.available_spare)?;
+        Some(depleted as f64 / (elapsed_ms as f64 / 3_600_000.0) * 1000.0)
+    }
+
+    pub fn temperature_exceeds_warning(&self) -> bool {
+        self.composite_temperature
+            >= self
+                .warning_temperature_threshold_kelvin
+                .unwrap_or(u16::MAX)
+    }
+
+    /// Bytes written, commonly shown as "NAND writes". Data units are reported in thousands of
+    /// 512 byte units.
+    pub fn nand_bytes_written_estimate(&self) -> u128 {
+        self.data_units_written.saturating_mul(512_000)
+    }
+
+    /// Ratio of `nand_bytes_written_estimate` to the bytes written by the host. A host count of 0
+    /// is treated as 1 byte.
+    pub fn write_amplification_factor_estimate(&self, host_written_bytes: u128) -> Option<f64> {
+        Some(self.nand_bytes_written_estimate() as f64 / host_written_bytes.max(1) as f64)
+    }
+
+    /// Controller busy time as a `Duration`. This saturates at `u64::MAX` seconds, which a drive
+    /// would only report after roughly 585 billion years of busy time.
+    pub fn busy_time_as_duration(&self) -> Duration {
+        Duration::from_secs(minutes_saturating_mul(
+            self.io_controller_busy_time_minutes,
+            60,
+        ))
+    }
+}
+
+#[derive(Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FwCommitInfo {
+    pub previous_revision: String,
+    pub new_revision: String,
+    // The commit action (CA) and firmware slot (FS) of the firmware commit command
+    pub commit_action: u8,
+    pub slot_number: u8,
+    // The status code type and status code the command completed with
+    pub status_code_type: u8,
+    pub commit_status: u8,
+    // Vendor assigned
+    pub commit_result: FwCommitResult,
+}
+
+impl FwCommitInfo {
+    pub fn command_succeeded(&self) -> bool {
+        self.status_code_type == 0 && self.commit_status == 0
+    }
+}
+
+// Vendor assigned firmware commit result. Its meaning is vendor specific, use
+// `FwCommitInfo::command_succeeded` for the status of the command
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FwCommitResult(pub u16);
+#[derive(Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TimestampChangeInfo {
+    // The event data, not decoded yet
+    pub data: Vec<u8>,
+}
+#[derive(Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PorInfo {
+    // Firmware revision running after the reset
+    pub firmware_revision: String,
+    pub resets: Vec<ControllerResetInfo>,
+}
+
+// One controller reset information descriptor of a power-on or reset event
+#[derive(Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ControllerResetInfo {
+    pub ctrl_id: u16,
+    // A firmware image was activated by the reset
+    pub firmware_activation: bool,
+    // Operation in progress when the reset happened
+    pub operation_in_progress: u8,
+    pub power_cycle_count: u32,
+    pub power_on_ms: u64,
+    pub timestamp: super::Timestamp,
+}
+#[derive(Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NvmHwErrorInfo {
+    // The event data, not decoded yet
+    pub data: Vec<u8>,
+}
+#[derive(Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ChangeNamespaceInfo {
+    // The event data, not decoded yet
+    pub data: Vec<u8>,
+}
+#[derive(Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FormatNvmStartInfo {
+    // The event data, not decoded yet
+    pub data: Vec<u8>,
+}
+#[derive(Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FormatNvmCompleteInfo {
+    pub nsid: u32,
+}
+#[derive(Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SanitizeStartInfo {
+    // The event data, not decoded yet
+    pub data: Vec<u8>,
+}
+#[derive(Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SanitizeCompleteInfo {
+    // The event data, not decoded yet
+    pub data: Vec<u8>,
+}
 #[derive(Debug)]
-pub struct SmartHealthInfo {}
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SetFeatureInfo {
+    pub feature: Feature,
+    pub save: bool,
+    // Number of logged command dwords, starting at CDW10
+    pub command_dwords: u8,
+    // Command dwords after CDW10, then the memory buffer
+    pub data: Vec<u8>,
+}
+
+impl SetFeatureInfo {
+    pub fn feature_id(&self) -> u8 {
+        self.feature.fid()
+    }
+
+    pub fn is_persistent(&self) -> bool {
+        self.save
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Feature {
+    Arbitration,
+    PowerManagement,
+    TemperatureThreshold,
+    VolatileWriteCache,
+    NumberOfQueues,
+    InterruptCoalescing,
+    AsyncEventConfig,
+    Timestamp,
+    Unknown { fid: u8 },
+}
+
+impl Feature {
+    pub fn from_fid(fid: u8) -> Self {
+        match fid {
+            0x01 => Self::Arbitration,
+            0x02 => Self::PowerManagement,
+            0x04 => Self::TemperatureThreshold,
+            0x06 => Self::VolatileWriteCache,
+            0x07 => Self::NumberOfQueues,
+            0x08 => Self::InterruptCoalescing,
+            0x0b => Self::AsyncEventConfig,
+            0x0e => Self::Timestamp,
+            _ => Self::Unknown { fid },
+        }
+    }
+
+    pub fn fid(&self) -> u8 {
+        match self {
+            Self::Arbitration => 0x01,
+            Self::PowerManagement => 0x02,
+            Self::TemperatureThreshold => 0x04,
+            Self::VolatileWriteCache => 0x06,
+            Self::NumberOfQueues => 0x07,
+            Self::InterruptCoalescing => 0x08,
+            Self::AsyncEventConfig => 0x0b,
+            Self::Timestamp => 0x0e,
+            Self::Unknown { fid } => *fid,
+        }
+    }
+}
+#[derive(Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TelementryLogCreatedInfo {
+    // The event data, not decoded yet
+    pub data: Vec<u8>,
+}
+// The event logs whole degrees Celsius, so the offset is rounded as well
+pub(crate) const KELVIN_OFFSET: u16 = 273;
+
+#[derive(Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ThermalExcursionInfo {
+    // Only one of these is set
+    pub over_threshold: bool,
+    pub under_threshold: bool,
+    pub threshold_kelvin: u16,
+}
+
+impl ThermalExcursionInfo {
+    pub fn threshold_celsius(&self) -> f32 {
+        self.threshold_kelvin as f32 - KELVIN_OFFSET as f32
+    }
+
+    /// Whether the drive went over a threshold above 75°C, a common drive spec limit.
+    pub fn is_critical(&self) -> bool {
+        self.over_threshold && self.threshold_kelvin > 75 + KELVIN_OFFSET
+    }
+}
 #[derive(Debug)]
-pub struct FwCommitInfo {}
-#[derive(Debug)]
-pub struct TimestampChangeInfo {}
-#[derive(Debug)]
-pub struct PorInfo {}
-#[derive(Debug)]
-pub struct NvmHwErrorInfo {}
-#[derive(Debug)]
-pub struct ChangeNamespaceInfo {}
-#[derive(Debug)]
-pub struct FormatNvmStartInfo {}
-#[derive(Debug)]
-pub struct FormatNvmCompleteInfo {}
-#[derive(Debug)]
-pub struct SanitizeStartInfo {}
-#[derive(Debug)]
-pub struct SanitizeCompleteInfo {}
-#[derive(Debug)]
-pub struct SetFeatureInfo {}
-#[derive(Debug)]
-pub struct TelementryLogCreatedInfo {}
-#[derive(Debug)]
-pub struct ThermalExcursionInfo {}
-#[derive(Debug)]
-pub struct VendorSpecifcInfo {}
-#[derive(Debug)]
-pub struct TcgDefinedInfo {}
-#[derive(Debug)]
-pub struct UnknownInfo {}
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VendorSpecifcInfo {
+    // PCI vendor id from the log header
+    pub vendor_id: u16,
+    pub data: Vec<u8>,
+}
+#[derive(Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TcgDefinedInfo {
+    // The event data, not decoded yet
+    pub data: Vec<u8>,
+}
+#[derive(Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UnknownInfo {
+    // The event type from the header, so the event can be written back out
+    pub event_type: u8,
+    pub data: Vec<u8>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spare_depletion_rate() {
+        let prev = SmartHealthInfo {
+            available_spare: 100,
+            ..Default::default()
+        };
+        let curr = SmartHealthInfo {
+            available_spare: 95,
+            ..Default::default()
+        };
+
+        // 5 points over 100 hours
+        let rate = curr
+            .spare_depletion_rate_per_1000_hours(&prev, 100 * 3_600_000)
+            .unwrap();
+        assert!((rate - 50.0).abs() < 1e-9);
+
+        // 5 points over 100,000 ms
+        let rate = curr
+            .spare_depletion_rate_per_1000_hours(&prev, 100_000)
+            .unwrap();
+        assert!((rate - 180_000.0).abs() < 1e-6);
+
+        assert_eq!(
+            curr.spare_depletion_rate_per_1000_hours(&curr, 1000),
+            Some(0.0)
+        );
+        assert_eq!(curr.spare_depletion_rate_per_1000_hours(&prev, 0), None);
+        assert_eq!(prev.spare_depletion_rate_per_1000_hours(&curr, 1000), None);
+    }
+
+    #[test]
+    fn test_temperature_exceeds_warning() {
+        let mut info = SmartHealthInfo {
+            composite_temperature: 350,
+            ..Default::default()
+        };
+        assert!(!info.temperature_exceeds_warning());
+
+        info.warning_temperature_threshold_kelvin = Some(343);
+        assert!(info.temperature_exceeds_warning());
+
+        info.warning_temperature_threshold_kelvin = Some(350);
+        assert!(info.temperature_exceeds_warning());
+
+        info.warning_temperature_threshold_kelvin = Some(351);
+        assert!(!info.temperature_exceeds_warning());
+    }
+
+    #[test]
+    fn test_nand_bytes_written_estimate() {
+        let mut info = SmartHealthInfo {
+            data_units_written: 2,
+            ..Default::default()
+        };
+        assert_eq!(info.nand_bytes_written_estimate(), 1_024_000);
+
+        info.data_units_written = u128::MAX;
+        assert_eq!(info.nand_bytes_written_estimate(), u128::MAX);
+    }
+
+    #[test]
+    fn test_write_amplification_factor_estimate() {
+        let info = SmartHealthInfo {
+            data_units_written: 3,
+            ..Default::default()
+        };
+        assert_eq!(
+            info.write_amplification_factor_estimate(1_024_000),
+            Some(1.5)
+        );
+        assert_eq!(
+            info.write_amplification_factor_estimate(1_536_000),
+            Some(1.0)
+        );
+        assert_eq!(
+            info.write_amplification_factor_estimate(0),
+            Some(1_536_000.0)
+        );
+    }
+
+    #[test]
+    fn test_critical_warning() {
+        let warning = CriticalWarning(0);
+        assert!(!warning.any());
+
+        let warning = CriticalWarning(0b0010_1001);
+        assert!(warning.any());
+        assert!(warning.available_spare_below_threshold());
+        assert!(!warning.temperature_threshold_exceeded());
+        assert!(!warning.reliability_degraded());
+        assert!(warning.read_only());
+        assert!(!warning.volatile_memory_backup_failed());
+        assert!(warning.persistent_memory_read_only());
+
+        let warning = CriticalWarning(0b0001_0110);
+        assert!(!warning.available_spare_below_threshold());
+        assert!(warning.temperature_threshold_exceeded());
+        assert!(warning.reliability_degraded());
+        assert!(warning.volatile_memory_backup_failed());
+
+        assert!(!CriticalWarning(0b1100_0000).any());
+    }
+
+    #[test]
+    fn test_set_feature_id() {
+        let info = SetFeatureInfo {
+            feature: Feature::PowerManagement,
+            save: true,
+            command_dwords: 1,
+            data: vec![],
+        };
+        assert_eq!(info.feature_id(), 2);
+        assert!(info.is_persistent());
+
+        let info = SetFeatureInfo {
+            feature: Feature::Unknown { fid: 0x55 },
+            save: false,
+            command_dwords: 1,
+            data: vec![1, 2, 3],
+        };
+        assert_eq!(info.feature_id(), 0x55);
+        assert!(!info.is_persistent());
+
+        for fid in 0..=u8::MAX {
+            assert_eq!(Feature::from_fid(fid).fid(), fid);
+        }
+    }
+
+    #[test]
+    fn test_busy_time_as_duration() {
+        let mut info = SmartHealthInfo {
+            io_controller_busy_time_minutes: 90,
+            ..Default::default()
+        };
+        assert_eq!(info.busy_time_as_duration(), Duration::from_secs(5400));
+
+        info.io_controller_busy_time_minutes = u64::MAX as u128 / 60;
+        assert_eq!(
+            info.busy_time_as_duration(),
+            Duration::from_secs(u64::MAX / 60 * 60)
+        );
+
+        info.io_controller_busy_time_minutes = u64::MAX as u128;
+        assert_eq!(info.busy_time_as_duration(), Duration::from_secs(u64::MAX));
+
+        info.io_controller_busy_time_minutes = u128::MAX;
+        assert_eq!(info.busy_time_as_duration(), Duration::from_secs(u64::MAX));
+    }
+
+    #[test]
+    fn test_thermal_excursion_over_threshold() {
+        let info = ThermalExcursionInfo {
+            over_threshold: true,
+            under_threshold: false,
+            threshold_kelvin: 358,
+        };
+        assert_eq!(info.threshold_celsius(), 85.0);
+        assert!(info.is_critical());
+
+        let info = ThermalExcursionInfo {
+            threshold_kelvin: 348,
+            ..info
+        };
+        assert!(!info.is_critical());
+    }
+
+    #[test]
+    fn test_thermal_excursion_under_threshold() {
+        let info = ThermalExcursionInfo {
+            over_threshold: false,
+            under_threshold: true,
+            threshold_kelvin: 273,
+        };
+        assert_eq!(info.threshold_celsius(), 0.0);
+        assert!(!info.is_critical());
+
+        let info = ThermalExcursionInfo {
+            threshold_kelvin: 400,
+            ..info
+        };
+        assert!(!info.is_critical());
+    }
+}
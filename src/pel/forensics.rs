@@ -0,0 +1,196 @@
+use std::time::Duration;
+
+use super::{Event, Pel};
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum CorruptionIndicator {
+    // Media and data integrity errors went up between two SMART / health events
+    MediaErrorsIncreased {
+        at: Duration,
+        previous: u128,
+        current: u128,
+    },
+    // The drive was reset while a sanitize was in progress
+    UnsafeShutdownAfterSanitize {
+        shutdown_ts: Duration,
+        sanitize_ts: Duration,
+    },
+    // Events identical to the event logged immediately before them
+    DuplicateEvents {
+        count: usize,
+    },
+    // A timestamp went backwards without a reset or timestamp change in between
+    MonotonicityViolation {
+        event1_ts: Duration,
+        event2_ts: Duration,
+    },
+}
+
+impl Pel {
+    /// Looks for event patterns that suggest data corruption or firmware bugs.
+    pub fn detect_potential_data_corruption(&self) -> Vec<CorruptionIndicator> {
+        let events = self.events.as_deref().unwrap_or_default();
+        let mut indicators = Vec::new();
+
+        let mut prev_media_errors = None;
+        let mut sanitize_ts = None;
+        for event in events {
+            match event {
+                Event::SmartHealth(smart) => {
+                    let current = smart.info.media_errors;
+                    if let Some(previous) = prev_media_errors.filter(|&prev| current > prev) {
+                        indicators.push(CorruptionIndicator::MediaErrorsIncreased {
+                            at: smart.timestamp.ms,
+                            previous,
+                            current,
+                        });
+                    }
+                    prev_media_errors = Some(current);
+                }
+                Event::SanitizeStart(start) => sanitize_ts = Some(start.timestamp.ms),
+                Event::SanitizeComplete(_) => sanitize_ts = None,
+                Event::Por(por) => {
+                    if let Some(sanitize_ts) = sanitize_ts.take() {
+                        indicators.push(CorruptionIndicator::UnsafeShutdownAfterSanitize {
+                            shutdown_ts: por.timestamp.ms,
+                            sanitize_ts,
+                        });
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        let duplicates = events
+            .windows(2)
+            .filter(|pair| is_duplicate(&pair[0], &pair[1]))
+            .count();
+        if duplicates > 0 {
+            indicators.push(CorruptionIndicator::DuplicateEvents { count: duplicates });
+        }
+
+        for pair in events.windows(2) {
+            // the timestamp is expected to restart after these
+            if matches!(pair[1], Event::Por(_) | Event::TimestampChange(_)) {
+                continue;
+            }
+            let (ts1, ts2) = (pair[0].timestamp(), pair[1].timestamp());
+            if ts2 < ts1 {
+                indicators.push(CorruptionIndicator::MonotonicityViolation {
+                    event1_ts: ts1.ms,
+                    event2_ts: ts2.ms,
+                });
+            }
+        }
+
+        indicators
+    }
+}
+
+fn is_duplicate(a: &Event, b: &Event) -> bool {
+    a.event_type() == b.event_type()
+        && a.event_type().is_some()
+        && a.ctrl_id() == b.ctrl_id()
+        && a.timestamp().equivalent_to(b.timestamp())
+        && record_field!(a, len) == record_field!(b, len)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pel::test_helpers::{event_at, record};
+    use crate::pel::{PorInfo, SanitizeCompleteInfo, SanitizeStartInfo, SmartHealthInfo};
+
+    #[test]
+    fn test_monotonicity_violation() {
+        let pel: Pel = [
+            event_at(100),
+            event_at(200),
+            event_at(150),
+            // a reset restarts the timestamp
+            Event::Por(record(10, PorInfo::default())),
+            event_at(20),
+        ]
+        .into_iter()
+        .collect();
+
+        assert_eq!(
+            pel.detect_potential_data_corruption(),
+            vec![CorruptionIndicator::MonotonicityViolation {
+                event1_ts: Duration::from_millis(200),
+                event2_ts: Duration::from_millis(150),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_media_errors_increased() {
+        let smart = |ms, media_errors| {
+            Event::SmartHealth(record(
+                ms,
+                SmartHealthInfo {
+                    media_errors,
+                    ..Default::default()
+                },
+            ))
+        };
+        let pel: Pel = [smart(0, 1), smart(10, 1), smart(20, 3)]
+            .into_iter()
+            .collect();
+
+        assert_eq!(
+            pel.detect_potential_data_corruption(),
+            vec![CorruptionIndicator::MediaErrorsIncreased {
+                at: Duration::from_millis(20),
+                previous: 1,
+                current: 3,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_unsafe_shutdown_after_sanitize() {
+        let pel: Pel = [
+            Event::SanitizeStart(record(0, SanitizeStartInfo::default())),
+            Event::SanitizeComplete(record(10, SanitizeCompleteInfo::default())),
+            Event::Por(record(20, PorInfo::default())),
+            Event::SanitizeStart(record(30, SanitizeStartInfo::default())),
+            Event::Por(record(40, PorInfo::default())),
+        ]
+        .into_iter()
+        .collect();
+
+        assert_eq!(
+            pel.detect_potential_data_corruption(),
+            vec![CorruptionIndicator::UnsafeShutdownAfterSanitize {
+                shutdown_ts: Duration::from_millis(40),
+                sanitize_ts: Duration::from_millis(30),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_duplicate_events() {
+        let pel: Pel = [
+            Event::Por(record(0, PorInfo::default())),
+            Event::Por(record(0, PorInfo::default())),
+            Event::Por(record(0, PorInfo::default())),
+            Event::Por(record(5, PorInfo::default())),
+        ]
+        .into_iter()
+        .collect();
+
+        assert_eq!(
+            pel.detect_potential_data_corruption(),
+            vec![CorruptionIndicator::DuplicateEvents { count: 2 }]
+        );
+    }
+
+    #[test]
+    fn test_no_indicators() {
+        assert!(Pel::default().detect_potential_data_corruption().is_empty());
+
+        let pel: Pel = (0..5).map(event_at).collect();
+        assert!(pel.detect_potential_data_corruption().is_empty());
+    }
+}
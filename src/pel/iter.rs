@@ -0,0 +1,275 @@
+use super::parser::{parse_event, parse_event_header};
+use super::vendor_info::VendorContext;
+use super::*;
+
+/// Parses events one at a time instead of collecting them all up front.
+pub struct PelEventIter<'a> {
+    input: &'a [u8],
+    // where `input` starts in the log and how many events were already returned, for errors
+    offset: usize,
+    index: u32,
+    remaining: u32,
+    vid: u16,
+    vendor: Option<VendorContext<'a>>,
+    headers_only: bool,
+}
+
+impl<'a> PelEventIter<'a> {
+    pub(crate) fn new(input: &'a [u8], offset: usize, remaining: u32, vid: u16) -> Self {
+        Self {
+            input,
+            offset,
+            index: 0,
+            remaining,
+            vid,
+            vendor: None,
+            headers_only: false,
+        }
+    }
+
+    /// Only parses the event headers. Every event is returned as `Event::Unknown`.
+    pub fn headers_only(self) -> Self {
+        Self {
+            headers_only: true,
+            ..self
+        }
+    }
+
+    pub(crate) fn with_vendor(self, vendor: Option<VendorContext<'a>>) -> Self {
+        Self { vendor, ..self }
+    }
+
+    fn parse_vendor_info(&self, event_type: u8, event: &mut Event) -> Result<(), PelError> {
+        let Some(vendor) = self.vendor else {
+            return Ok(());
+        };
+        let revision = *record_field!(event, revision);
+        if let Some(info) = record_field!(event, vendor_info, mut) {
+            info.parsed = Some(vendor.parser.parse(
+                vendor.vid,
+                vendor.ssvid,
+                event_type,
+                revision,
+                &info.raw,
+            )?);
+        }
+        Ok(())
+    }
+}
+
+impl<'a> Iterator for PelEventIter<'a> {
+    type Item = Result<Event, PelError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+        let (index, offset) = (self.index, self.offset);
+
+        let (event_type, header) = match parse_event_header(self.input) {
+            Ok((_, header)) => header,
+            Err(err) => {
+                // without a header there's no way to find the next event
+                self.remaining = 0;
+                if self.input.len() < EVENT_HEADER_LEN {
+                    return Some(Err(PelError::InsufficientData {
+                        needed: EVENT_HEADER_LEN - self.input.len(),
+                    }));
+                }
+                return Some(Err(err.into()));
+            }
+        };
+        let len = header.len;
+        if self.input.len() < len as usize {
+            self.remaining = 0;
+            return Some(Err(PelError::EventBodyTooShort {
+                index,
+                offset,
+                event_type,
+                declared_len: len,
+                available: self.input.len(),
+            }));
+        }
+
+        // advance by the declared length so a bad body doesn't throw off the next event
+        let (event, rest) = self.input.split_at(len as usize);
+        self.input = rest;
+        self.offset += len as usize;
+        self.index += 1;
+
+        let event_len = len - header.header_len as u16;
+        if !self.headers_only && header.vendor_info_len > event_len {
+            return Some(Err(PelError::VendorInfoTooLong {
+                index,
+                offset,
+                vendor_info_len: header.vendor_info_len,
+                event_len,
+            }));
+        }
+        let result = parse_event(event, self.vid, self.headers_only)
+            .map_err(|err| match PelError::from(err) {
+                PelError::NomError(kind) => PelError::InvalidEventData {
+                    index,
+                    offset,
+                    event_type,
+                    kind,
+                },
+                err => err,
+            })
+            .and_then(|(_, mut event)| {
+                self.parse_vendor_info(event_type, &mut event)?;
+                Ok(event)
+            });
+        Some(result)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.remaining as usize))
+    }
+}
+
+impl Pel {
+    /// Iterates over the events in `event_data`, which starts right after the log header.
+    pub fn iter_events<'a>(&self, event_data: &'a [u8]) -> PelEventIter<'a> {
+        let offset = (self.header_len as usize).max(MINIMUM_LOG_SIZE as usize);
+        PelEventIter::new(event_data, offset, self.num_events, self.vid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nom::error::ErrorKind;
+
+    use super::*;
+    use crate::pel::test_helpers::*;
+
+    fn parse(events: &[Vec<u8>]) -> (Pel, Vec<u8>) {
+        let input = make_full_pel(events);
+        let (event_data, pel) = parse_log_header(&input).unwrap();
+        (pel, event_data.to_vec())
+    }
+
+    #[test]
+    fn test_iter_events() {
+        let (pel, event_data) = parse(&[
+            make_smart_event_bytes(1, 1, 1000),
+            make_fw_commit_event_bytes(1, 1, 2000, "1.0", "2.0"),
+            make_event_bytes(POR, 1, 2, 3000, &[0u8; 16]),
+        ]);
+
+        {
+            // stop early and drop the iterator with events left
+            let mut iter = pel.iter_events(&event_data);
+            assert!(matches!(iter.next(), Some(Ok(Event::SmartHealth(_)))));
+            assert!(matches!(iter.next(), Some(Ok(Event::FwCommit(_)))));
+        }
+
+        let events: Vec<_> = pel.iter_events(&event_data).collect();
+        assert_eq!(events.len(), 3);
+        assert!(matches!(events[2], Ok(Event::Por(_))));
+    }
+
+    #[test]
+    fn test_iter_events_headers_only() {
+        let mut bad = make_event_bytes(POR, 1, 1, 1000, &[0u8; 4]);
+        // bodies aren't looked at, so a bad vendor info length doesn't matter
+        bad[20..22].copy_from_slice(&8u16.to_le_bytes());
+        let (pel, event_data) = parse(&[bad, make_smart_event_bytes(1, 1, 2000)]);
+
+        let events: Vec<_> = pel
+            .iter_events(&event_data)
+            .headers_only()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(events.len(), 2);
+        assert!(matches!(events[1], Event::Unknown(_)));
+        assert_eq!(events[1].timestamp().millis(), 2000);
+    }
+
+    #[test]
+    fn test_iter_events_stops_at_num_events() {
+        let (mut pel, event_data) = parse(&[
+            make_smart_event_bytes(1, 1, 1000),
+            make_smart_event_bytes(1, 1, 2000),
+        ]);
+        pel.num_events = 1;
+
+        let mut iter = pel.iter_events(&event_data);
+        assert!(iter.next().unwrap().is_ok());
+        assert!(iter.next().is_none());
+    }
+
+    #[test]
+    fn test_iter_events_bad_event() {
+        let mut bad = make_event_bytes(POR, 1, 1, 1000, &[0u8; 4]);
+        // 21:20 - vendor specific information length longer than the event
+        bad[20..22].copy_from_slice(&8u16.to_le_bytes());
+        let (pel, event_data) = parse(&[bad, make_smart_event_bytes(1, 1, 2000)]);
+
+        let mut iter = pel.iter_events(&event_data);
+        assert_eq!(
+            iter.next().unwrap().unwrap_err(),
+            PelError::VendorInfoTooLong {
+                index: 0,
+                offset: LOG_HEADER_LEN,
+                vendor_info_len: 8,
+                event_len: 4
+            }
+        );
+        // the next event is still found
+        let event = iter.next().unwrap().unwrap();
+        assert_eq!(event.timestamp().millis(), 2000);
+        assert!(iter.next().is_none());
+    }
+
+    #[test]
+    fn test_iter_events_invalid_data() {
+        let (pel, event_data) = parse(&[
+            make_smart_event_bytes(1, 1, 1000),
+            // too short for the firmware revisions
+            make_event_bytes(FW_COMMIT, 1, 1, 2000, &[0u8; 12]),
+        ]);
+
+        let mut iter = pel.iter_events(&event_data);
+        assert!(iter.next().unwrap().is_ok());
+        assert_eq!(
+            iter.next().unwrap().unwrap_err(),
+            PelError::InvalidEventData {
+                index: 1,
+                offset: LOG_HEADER_LEN + EVENT_HEADER_LEN + SMART_EVENT_LEN,
+                event_type: FW_COMMIT,
+                kind: ErrorKind::Eof
+            }
+        );
+    }
+
+    #[test]
+    fn test_iter_events_truncated() {
+        let (pel, event_data) = parse(&[
+            make_smart_event_bytes(1, 1, 1000),
+            make_smart_event_bytes(1, 1, 2000),
+        ]);
+
+        let mut iter = pel.iter_events(&event_data[..event_data.len() - 1]);
+        assert!(iter.next().unwrap().is_ok());
+        assert_eq!(
+            iter.next().unwrap().unwrap_err(),
+            PelError::EventBodyTooShort {
+                index: 1,
+                offset: LOG_HEADER_LEN + EVENT_HEADER_LEN + SMART_EVENT_LEN,
+                event_type: SMART_HEALTH,
+                declared_len: 536,
+                available: 535
+            }
+        );
+        assert!(iter.next().is_none());
+
+        let mut iter = pel.iter_events(&event_data[..10]);
+        assert_eq!(
+            iter.next().unwrap().unwrap_err(),
+            PelError::InsufficientData { needed: 14 }
+        );
+        assert!(iter.next().is_none());
+    }
+}
@@ -1,22 +1,133 @@
+// Accesses a field of the `EventRecord` shared by every variant, `record_field!(event, field, mut)`
+// for a mutable reference. Defined ahead of the submodules so they can use it too
+macro_rules! record_field {
+    ($event:expr, $field:ident $(, $mut:tt)?) => {
+        match $event {
+            Event::SmartHealth(record) => &$($mut)? record.$field,
+            Event::FwCommit(record) => &$($mut)? record.$field,
+            Event::TimestampChange(record) => &$($mut)? record.$field,
+            Event::Por(record) => &$($mut)? record.$field,
+            Event::NvmHwError(record) => &$($mut)? record.$field,
+            Event::ChangeNamespace(record) => &$($mut)? record.$field,
+            Event::FormatNvmStart(record) => &$($mut)? record.$field,
+            Event::FormatNvmComplete(record) => &$($mut)? record.$field,
+            Event::SanitizeStart(record) => &$($mut)? record.$field,
+            Event::SanitizeComplete(record) => &$($mut)? record.$field,
+            Event::SetFeature(record) => &$($mut)? record.$field,
+            Event::TelementryLogCreated(record) => &$($mut)? record.$field,
+            Event::ThermalExcursion(record) => &$($mut)? record.$field,
+            Event::VendorSpecifc(record) => &$($mut)? record.$field,
+            Event::TcgDefined(record) => &$($mut)? record.$field,
+            Event::Unknown(record) => &$($mut)? record.$field,
+        }
+    };
+}
+
+mod display;
+mod error;
 mod events;
+mod forensics;
+mod iter;
+#[cfg(feature = "serde_json")]
+mod nvme_cli;
 mod parser;
+mod serialize;
+mod stats;
+#[cfg(test)]
+pub(crate) mod test_helpers;
+mod vendor_info;
+mod vendors;
 
-use self::parser::{parse_event_header, parse_log_header};
+use self::parser::parse_log_header;
+use self::vendor_info::VendorContext;
 use nom::{
     bits,
     bytes::complete::take,
     sequence::{preceded, tuple},
     IResult,
 };
-use std::{default, time::Duration};
+use std::{
+    collections::{BTreeMap, HashMap},
+    time::Duration,
+};
 
+pub use self::error::{PelError, PEL_LOG_ID};
 pub use self::events::*;
+pub use self::forensics::CorruptionIndicator;
+pub use self::iter::PelEventIter;
+pub use self::serialize::{EventInfo, ToBytesLE};
+pub use self::stats::PelStats;
+pub use self::vendor_info::{VendorInfo, VendorInfoParser, VendorRegistry};
+
+/// Parses a full persistent event log, including every event.
+pub fn parse_pel(input: &[u8]) -> Result<Pel, PelError> {
+    parse_pel_with_vendor(input, &VendorRegistry::default())
+}
+
+/// Like [`parse_pel`], but decodes vendor specific information with the parser registered for
+/// the log's vendor, if there is one.
+pub fn parse_pel_with_vendor(input: &[u8], registry: &VendorRegistry) -> Result<Pel, PelError> {
+    parse_log(input, registry, false)
+}
+
+/// Like [`parse_pel`], but skips the event data. Every event is parsed as `Event::Unknown` with
+/// only the header fields filled in.
+pub fn parse_pel_headers_only(input: &[u8]) -> Result<Pel, PelError> {
+    parse_log(input, &VendorRegistry::default(), true)
+}
+
+fn parse_log(input: &[u8], registry: &VendorRegistry, headers_only: bool) -> Result<Pel, PelError> {
+    if input.len() < MINIMUM_LOG_SIZE as usize {
+        return Err(PelError::InsufficientData {
+            needed: MINIMUM_LOG_SIZE as usize - input.len(),
+        });
+    }
+    if input[0] != PEL_LOG_ID {
+        return Err(PelError::InvalidLogId {
+            expected: PEL_LOG_ID,
+            got: input[0],
+        });
+    }
 
-pub fn parse_pel(input: &[u8]) -> IResult<&[u8], Pel> {
-    todo!()
+    let (input, mut pel) = parse_log_header(input)?;
+    // the header can be longer than the fields we know about
+    let extra_header_len = (pel.header_len as usize).saturating_sub(MINIMUM_LOG_SIZE as usize);
+    if input.len() < extra_header_len {
+        return Err(PelError::InsufficientData {
+            needed: extra_header_len - input.len(),
+        });
+    }
+    let input = &input[extra_header_len..];
+
+    // don't trust TNEV for the allocation, every event is at least a header long
+    let mut events =
+        Vec::with_capacity((pel.num_events as usize).min(input.len() / EVENT_HEADER_LEN));
+    let vendor = registry
+        .get(pel.vid, pel.ssvid)
+        .map(|parser| VendorContext {
+            parser,
+            vid: pel.vid,
+            ssvid: pel.ssvid,
+        });
+    let mut iter = pel.iter_events(input).with_vendor(vendor);
+    if headers_only {
+        iter = iter.headers_only();
+    }
+    for event in iter {
+        events.push(event?);
+    }
+    pel.events = Some(events);
+    if !headers_only {
+        pel.computed_checksum = Some(pel.compute_log_checksum());
+    }
+    #[cfg(debug_assertions)]
+    pel.assert_invariants();
+
+    Ok(pel)
 }
 
 #[derive(Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Pel {
     pub num_events: u32,
     pub len: u64,
@@ -29,22 +140,410 @@ pub struct Pel {
     pub ssvid: u16,
     pub serial_num: String,
     pub model_num: String,
-    pub name: String,
+    pub subnqn: String,
     pub supp_events: SuppEventsBitmap,
     pub events: Option<Vec<Event>>,
     // Added in 2
     pub generation: Option<u16>,
     pub reporting_context: Option<ReportingContext>,
+    // CRC32 of the event records when the log was parsed, see `compute_log_checksum`
+    pub computed_checksum: Option<u32>,
 }
 
-#[derive(Debug)]
+// The log header alone is 512 bytes
+pub const MINIMUM_LOG_SIZE: u64 = 512;
+// Event headers are 24 bytes for every event revision we know about
+pub(crate) const EVENT_HEADER_LEN: usize = 24;
+
+impl Pel {
+    pub fn total_log_size_bytes(&self) -> u64 {
+        self.len
+    }
+
+    /// Size of the event records following the log header, or `None` if the reported log length
+    /// is shorter than the header.
+    pub fn events_section_size_bytes(&self) -> Option<u64> {
+        self.len.checked_sub(self.header_len as u64)
+    }
+
+    /// The NVM subsystem NVMe qualified name (SUBNQN).
+    pub fn subnqn(&self) -> &str {
+        self.subnqn.trim()
+    }
+
+    #[deprecated(note = "use `subnqn` instead")]
+    pub fn name(&self) -> &str {
+        self.subnqn()
+    }
+
+    // Covers both `nqn.YYYY-MM.reverse.domain:suffix` and the UUID based
+    // `nqn.2014-08.org.nvmexpress:uuid:` format
+    pub fn subnqn_is_well_formed(&self) -> bool {
+        self.subnqn().starts_with("nqn.")
+    }
+
+    /// Name of the PCI vendor, if it's a known SSD vendor.
+    pub fn vid_name(&self) -> Option<&'static str> {
+        vendors::lookup(vendors::PCI_VENDORS, self.vid)
+    }
+
+    /// Name of the PCI subsystem vendor, if it's a known server OEM.
+    pub fn ssvid_name(&self) -> Option<&'static str> {
+        vendors::lookup(vendors::PCI_SUBSYSTEM_VENDORS, self.ssvid)
+    }
+
+    /// Whether the drive was sold through a system integrator rather than as a standalone drive.
+    pub fn is_oem_device(&self) -> bool {
+        self.ssvid != self.vid
+    }
+
+    /// Describes the drive as `"{vendor} {model} (SN: {serial})"`.
+    pub fn vendor_name(&self) -> String {
+        let vendor = match self.vid_name() {
+            Some(name) => name.to_string(),
+            None => format!("VID:{:#06X}", self.vid),
+        };
+        format!("{vendor} {} (SN: {})", self.model_num, self.serial_num)
+    }
+
+    /// Compact identifier in the form `"{serial}@{model}"`.
+    pub fn device_id(&self) -> String {
+        format!("{}@{}", self.serial_num, self.model_num)
+    }
+
+    /// The lowest and highest controller IDs across all events.
+    pub fn controller_id_range(&self) -> Option<(u16, u16)> {
+        self.events
+            .iter()
+            .flatten()
+            .map(Event::ctrl_id)
+            .fold(None, |range, id| match range {
+                None => Some((id, id)),
+                Some((min, max)) => Some((min.min(id), max.max(id))),
+            })
+    }
+
+    /// Number of controller IDs spanned by `controller_id_range`. This overestimates if some IDs
+    /// in the range never appear in the log.
+    pub fn expected_controller_count(&self) -> usize {
+        self.controller_id_range()
+            .map_or(0, |(min, max)| (max - min) as usize + 1)
+    }
+
+    /// Number of events reported by the log header.
+    pub fn reported_event_count(&self) -> u32 {
+        self.num_events
+    }
+
+    /// Number of events actually parsed.
+    pub fn actual_event_count(&self) -> usize {
+        self.events.as_ref().map_or(0, Vec::len)
+    }
+
+    /// Number of events parsed. Not to be confused with the `len` field, which is the total log
+    /// length in bytes.
+    pub fn len(&self) -> usize {
+        self.actual_event_count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn has_events(&self) -> bool {
+        !self.is_empty()
+    }
+
+    pub fn is_num_events_consistent(&self) -> bool {
+        self.num_events == self.events.as_ref().map_or(0, |v| v.len() as u32)
+    }
+
+    pub fn first_event_timestamp(&self) -> Option<&Timestamp> {
+        self.events.as_ref()?.first().map(Event::timestamp)
+    }
+
+    pub fn last_event_timestamp(&self) -> Option<&Timestamp> {
+        self.events.as_ref()?.last().map(Event::timestamp)
+    }
+
+    /// Power on hours elapsed since the first event was logged. This assumes the log was captured
+    /// at the moment `power_on_hours` was recorded.
+    pub fn oldest_event_age_hours(&self) -> Option<f64> {
+        let ts = self.first_event_timestamp()?;
+        Some(self.power_on_hours as f64 - ts.millis() as f64 / 3_600_000.0)
+    }
+
+    /// Power on hours elapsed since the last event was logged, with the same assumption as
+    /// `oldest_event_age_hours`.
+    pub fn newest_event_age_hours(&self) -> Option<f64> {
+        let ts = self.last_event_timestamp()?;
+        Some(self.power_on_hours as f64 - ts.millis() as f64 / 3_600_000.0)
+    }
+
+    pub fn events_per_power_on_hour(&self) -> f64 {
+        self.actual_event_count() as f64 / self.power_on_hours.max(1) as f64
+    }
+
+    pub fn events_per_power_cycle(&self) -> f64 {
+        self.actual_event_count() as f64 / self.power_cycle_count.max(1) as f64
+    }
+
+    /// Consecutive pairs of events logged within `max_duration_ms` of each other.
+    pub fn events_with_short_duration(&self, max_duration_ms: u64) -> Vec<(&Event, &Event)> {
+        self.events
+            .as_deref()
+            .unwrap_or_default()
+            .windows(2)
+            .filter(|pair| {
+                pair[0]
+                    .timestamp()
+                    .millis()
+                    .abs_diff(pair[1].timestamp().millis())
+                    <= max_duration_ms
+            })
+            .map(|pair| (&pair[0], &pair[1]))
+            .collect()
+    }
+
+    /// Events from `from_index` onwards, for polling the log incrementally.
+    pub fn events_since_index(&self, from_index: u32) -> &[Event] {
+        self.events
+            .as_deref()
+            .unwrap_or_default()
+            .get(from_index as usize..)
+            .unwrap_or_default()
+    }
+
+    /// Number of events logged strictly before `ts`.
+    pub fn count_events_before(&self, ts: &Timestamp) -> usize {
+        self.events
+            .iter()
+            .flatten()
+            .filter(|event| event.timestamp().millis() < ts.millis())
+            .count()
+    }
+
+    /// Number of events logged strictly after `ts`.
+    pub fn count_events_after(&self, ts: &Timestamp) -> usize {
+        self.events
+            .iter()
+            .flatten()
+            .filter(|event| event.timestamp().millis() > ts.millis())
+            .count()
+    }
+
+    /// Number of events logged at or after `start` and before `end`.
+    pub fn count_events_between(&self, start: &Timestamp, end: &Timestamp) -> usize {
+        self.events
+            .iter()
+            .flatten()
+            .filter(|event| (start.millis()..end.millis()).contains(&event.timestamp().millis()))
+            .count()
+    }
+
+    /// The last power on or reset event and its index in the log.
+    pub fn latest_por_event(&self) -> Option<(usize, &PorEvent)> {
+        self.events
+            .as_deref()
+            .unwrap_or_default()
+            .iter()
+            .enumerate()
+            .rev()
+            .find_map(|(i, event)| match event {
+                Event::Por(event) => Some((i, event)),
+                _ => None,
+            })
+    }
+
+    /// Events following the last power on or reset, or every event if there wasn't one.
+    pub fn current_session_events(&self) -> &[Event] {
+        let events = self.events.as_deref().unwrap_or_default();
+        match self.latest_por_event() {
+            Some((i, _)) => &events[i + 1..],
+            None => events,
+        }
+    }
+
+    fn fw_commit_events(&self) -> impl DoubleEndedIterator<Item = &FwCommitEvent> {
+        self.events
+            .iter()
+            .flatten()
+            .filter_map(|event| match event {
+                Event::FwCommit(event) => Some(event),
+                _ => None,
+            })
+    }
+
+    /// Firmware revision activated by the most recent successful commit.
+    pub fn latest_fw_revision(&self) -> Option<String> {
+        self.fw_commit_events()
+            .rev()
+            .find(|event| event.info.command_succeeded())
+            .map(|event| event.info.new_revision.clone())
+    }
+
+    /// Firmware revision the drive was running before the first logged commit.
+    pub fn initial_fw_revision(&self) -> Option<String> {
+        self.fw_commit_events()
+            .next()
+            .map(|event| event.info.previous_revision.clone())
+    }
+
+    /// Events following the last completed format of namespace `nsid`, or every event if it was
+    /// never formatted.
+    pub fn events_after_format_nvm(&self, nsid: u32) -> Vec<&Event> {
+        let events = self.events.as_deref().unwrap_or_default();
+        let last_format = events.iter().rposition(
+            |event| matches!(event, Event::FormatNvmComplete(event) if event.info.nsid == nsid),
+        );
+        match last_format {
+            Some(i) => self.events_since_index(i as u32 + 1).iter().collect(),
+            None => events.iter().collect(),
+        }
+    }
+
+    pub fn get(&self, index: usize) -> Option<&Event> {
+        self.events.as_ref()?.get(index)
+    }
+
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        if !self.is_num_events_consistent() {
+            return Err(ValidationError::EventCountMismatch {
+                reported: self.reported_event_count(),
+                actual: self.actual_event_count(),
+            });
+        }
+        // devices can log types their bitmap leaves out, so this isn't checked while parsing
+        let events = self.events.as_deref().unwrap_or_default();
+        for (index, event) in events.iter().enumerate() {
+            let event_type = event.type_code();
+            if EventType::try_from(event_type).is_ok() && !self.supp_events.is_supported(event_type)
+            {
+                return Err(ValidationError::UnsupportedEventType { index, event_type });
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks the internal consistency of the log in debug builds.
+    pub fn assert_invariants(&self) {
+        let events = self.events.as_deref().unwrap_or_default();
+        debug_assert_eq!(
+            self.num_events,
+            events.len() as u32,
+            "num_events doesn't match the number of events"
+        );
+        for event in events {
+            // timestamps are durations, so they can't be negative
+            debug_assert_ne!(*record_field!(event, len), 0, "event has a length of 0");
+        }
+    }
+
+    fn smart_events(&self) -> impl DoubleEndedIterator<Item = &SmartHealthEvent> {
+        self.events
+            .iter()
+            .flatten()
+            .filter_map(|event| match event {
+                Event::SmartHealth(event) => Some(event),
+                _ => None,
+            })
+    }
+
+    /// The last SMART / health event by position in the log.
+    pub fn latest_smart_event(&self) -> Option<&SmartHealthEvent> {
+        self.smart_events().next_back()
+    }
+
+    /// The first SMART / health event by position in the log.
+    pub fn earliest_smart_event(&self) -> Option<&SmartHealthEvent> {
+        self.smart_events().next()
+    }
+
+    /// Number of events logged in each hour since power on, keyed by hour.
+    pub fn events_count_by_hour(&self) -> BTreeMap<u64, usize> {
+        let mut histogram = BTreeMap::new();
+        for event in self.events.iter().flatten() {
+            *histogram
+                .entry(event.timestamp().millis() / 3_600_000)
+                .or_default() += 1;
+        }
+        histogram
+    }
+
+    pub fn vendor_specific_events_by_vid(&self) -> HashMap<u16, Vec<&VendorSpecifcEvent>> {
+        let mut by_vid: HashMap<u16, Vec<&VendorSpecifcEvent>> = HashMap::new();
+        for event in self.events.iter().flatten() {
+            if let Event::VendorSpecifc(event) = event {
+                by_vid.entry(event.info.vendor_id).or_default().push(event);
+            }
+        }
+        by_vid
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ValidationError {
+    EventCountMismatch { reported: u32, actual: usize },
+    UnsupportedEventType { index: usize, event_type: u8 },
+}
+
+impl std::ops::Index<usize> for Pel {
+    type Output = Event;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        let events = self
+            .events
+            .as_ref()
+            .expect("events weren't parsed, only the log header");
+        events.get(index).unwrap_or_else(|| {
+            panic!(
+                "event index {index} out of bounds for a log with {} events",
+                events.len()
+            )
+        })
+    }
+}
+
+impl Extend<Event> for Pel {
+    fn extend<T: IntoIterator<Item = Event>>(&mut self, iter: T) {
+        let events = self.events.get_or_insert_with(Vec::new);
+        let prev_len = events.len();
+        events.extend(iter);
+        self.num_events += (events.len() - prev_len) as u32;
+    }
+}
+
+impl FromIterator<Event> for Pel {
+    fn from_iter<T: IntoIterator<Item = Event>>(iter: T) -> Self {
+        let mut pel = Pel::default();
+        pel.extend(iter);
+        pel
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ReportingContext {
     DoesNotExist,
     NVMPort(u16),
     MiPort(u16),
 }
 
+impl From<u32> for ReportingContext {
+    fn from(value: u32) -> Self {
+        // 15:00 - port identifier
+        let port = value as u16;
+        // 18 - reporting context exists, 17:16 - port identifier type
+        match (value >> 18 & 0x1, value >> 16 & 0x3) {
+            (1, 0b01) => Self::NVMPort(port),
+            (1, 0b10) => Self::MiPort(port),
+            _ => Self::DoesNotExist,
+        }
+    }
+}
+
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct EventRecord<T> {
     pub revision: u8,
     pub header_len: u8, // The total event header length (EHL+ 3)
@@ -52,9 +551,27 @@ pub struct EventRecord<T> {
     pub timestamp: Timestamp,
     pub vendor_info_len: u16,
     pub len: u16, // The total event length (EL + EHL +3)
+    pub vendor_info: Option<VendorInfo>,
+    // serde serializes a Box as its contents
     pub info: Box<T>,
 }
 
+impl EventRecord<()> {
+    // Attaches the parsed event data to a parsed event header
+    fn with_info<T>(self, info: T) -> EventRecord<T> {
+        EventRecord {
+            revision: self.revision,
+            header_len: self.header_len,
+            ctrl_id: self.ctrl_id,
+            timestamp: self.timestamp,
+            vendor_info_len: self.vendor_info_len,
+            len: self.len,
+            vendor_info: self.vendor_info,
+            info: Box::new(info),
+        }
+    }
+}
+
 pub type SmartHealthEvent = EventRecord<SmartHealthInfo>;
 pub type FwCommitEvent = EventRecord<FwCommitInfo>;
 pub type TimestampChangeEvent = EventRecord<TimestampChangeInfo>;
@@ -73,6 +590,7 @@ pub type TcgDefinedEvent = EventRecord<TcgDefinedInfo>;
 pub type UnknownEvent = EventRecord<UnknownInfo>;
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Event {
     SmartHealth(SmartHealthEvent),
     FwCommit(FwCommitEvent),
@@ -92,16 +610,194 @@ pub enum Event {
     Unknown(UnknownEvent),
 }
 
+impl Event {
+    pub fn ctrl_id(&self) -> u16 {
+        *record_field!(self, ctrl_id)
+    }
+
+    pub fn timestamp(&self) -> &Timestamp {
+        record_field!(self, timestamp)
+    }
+
+    /// The event type code from the event header, which unknown events and events from
+    /// [`parse_pel_headers_only`] keep as well.
+    pub fn type_code(&self) -> u8 {
+        match self {
+            Event::Unknown(record) => record.info.event_type,
+            _ => self.event_type().map(u8::from).unwrap_or_default(),
+        }
+    }
+
+    // The decoded event type, `None` for `Event::Unknown` even if the type code is known
+    pub fn event_type(&self) -> Option<EventType> {
+        match self {
+            Event::SmartHealth(_) => Some(EventType::SmartHealth),
+            Event::FwCommit(_) => Some(EventType::FwCommit),
+            Event::TimestampChange(_) => Some(EventType::TimestampChange),
+            Event::Por(_) => Some(EventType::Por),
+            Event::NvmHwError(_) => Some(EventType::NvmHwError),
+            Event::ChangeNamespace(_) => Some(EventType::ChangeNamespace),
+            Event::FormatNvmStart(_) => Some(EventType::FormatNvmStart),
+            Event::FormatNvmComplete(_) => Some(EventType::FormatNvmComplete),
+            Event::SanitizeStart(_) => Some(EventType::SanitizeStart),
+            Event::SanitizeComplete(_) => Some(EventType::SanitizeComplete),
+            Event::SetFeature(_) => Some(EventType::SetFeature),
+            Event::TelementryLogCreated(_) => Some(EventType::TelementryLogCreated),
+            Event::ThermalExcursion(_) => Some(EventType::ThermalExcursion),
+            Event::VendorSpecifc(_) => Some(EventType::VendorSpecific),
+            Event::TcgDefined(_) => Some(EventType::TcgDefined),
+            Event::Unknown(_) => None,
+        }
+    }
+}
+
+// Converts an event into its record, handing the event back if it's a different type
+macro_rules! impl_try_from_event {
+    ($($variant:ident => $record:ty,)*) => {
+        $(
+            impl TryFrom<Event> for $record {
+                type Error = Event;
+
+                fn try_from(event: Event) -> Result<Self, Self::Error> {
+                    match event {
+                        Event::$variant(record) => Ok(record),
+                        event => Err(event),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_try_from_event! {
+    SmartHealth => SmartHealthEvent,
+    FwCommit => FwCommitEvent,
+    TimestampChange => TimestampChangeEvent,
+    Por => PorEvent,
+    NvmHwError => NvmHwErrorEvent,
+    ChangeNamespace => ChangeNamespaceEvent,
+    FormatNvmStart => FormatNvmStartEvent,
+    FormatNvmComplete => FormatNvmCompleteEvent,
+    SanitizeStart => SanitizeStartEvent,
+    SanitizeComplete => SanitizeCompleteEvent,
+    SetFeature => SetFeatureEvent,
+    TelementryLogCreated => TelementryLogCreatedEvent,
+    ThermalExcursion => ThermalExcursionEvent,
+    VendorSpecifc => VendorSpecifcEvent,
+    TcgDefined => TcgDefinedEvent,
+    Unknown => UnknownEvent,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum EventType {
+    SmartHealth,
+    FwCommit,
+    TimestampChange,
+    Por,
+    NvmHwError,
+    ChangeNamespace,
+    FormatNvmStart,
+    FormatNvmComplete,
+    SanitizeStart,
+    SanitizeComplete,
+    SetFeature,
+    TelementryLogCreated,
+    ThermalExcursion,
+    VendorSpecific,
+    TcgDefined,
+}
+
+impl EventType {
+    pub fn is_vendor_defined(&self) -> bool {
+        matches!(self, EventType::VendorSpecific)
+    }
+
+    pub fn is_tcg_defined(&self) -> bool {
+        matches!(self, EventType::TcgDefined)
+    }
+
+    pub fn is_standard(&self) -> bool {
+        !self.is_vendor_defined() && !self.is_tcg_defined()
+    }
+}
+
+impl TryFrom<u8> for EventType {
+    type Error = u8;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            SMART_HEALTH => Ok(Self::SmartHealth),
+            FW_COMMIT => Ok(Self::FwCommit),
+            TIMESTAMP_CHANGE => Ok(Self::TimestampChange),
+            POR => Ok(Self::Por),
+            NVM_HW_ERROR => Ok(Self::NvmHwError),
+            CHANGE_NAMESPACE => Ok(Self::ChangeNamespace),
+            FORMAT_NVM_START => Ok(Self::FormatNvmStart),
+            FORMAT_NVM_COMPLETE => Ok(Self::FormatNvmComplete),
+            SANITIZE_START => Ok(Self::SanitizeStart),
+            SANITIZE_COMPLETE => Ok(Self::SanitizeComplete),
+            SET_FEATURE => Ok(Self::SetFeature),
+            TELEMENTRY_LOG_CREATED => Ok(Self::TelementryLogCreated),
+            THERMAL_EXCURSION => Ok(Self::ThermalExcursion),
+            VENDOR_SPECIFC => Ok(Self::VendorSpecific),
+            TCG_DEFINED => Ok(Self::TcgDefined),
+            _ => Err(value),
+        }
+    }
+}
+
+impl From<EventType> for u8 {
+    fn from(value: EventType) -> Self {
+        match value {
+            EventType::SmartHealth => SMART_HEALTH,
+            EventType::FwCommit => FW_COMMIT,
+            EventType::TimestampChange => TIMESTAMP_CHANGE,
+            EventType::Por => POR,
+            EventType::NvmHwError => NVM_HW_ERROR,
+            EventType::ChangeNamespace => CHANGE_NAMESPACE,
+            EventType::FormatNvmStart => FORMAT_NVM_START,
+            EventType::FormatNvmComplete => FORMAT_NVM_COMPLETE,
+            EventType::SanitizeStart => SANITIZE_START,
+            EventType::SanitizeComplete => SANITIZE_COMPLETE,
+            EventType::SetFeature => SET_FEATURE,
+            EventType::TelementryLogCreated => TELEMENTRY_LOG_CREATED,
+            EventType::ThermalExcursion => THERMAL_EXCURSION,
+            EventType::VendorSpecific => VENDOR_SPECIFC,
+            EventType::TcgDefined => TCG_DEFINED,
+        }
+    }
+}
+
 // TODO: use a set or something else
 #[derive(Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SuppEventsBitmap([u8; 32]);
 
+impl SuppEventsBitmap {
+    pub fn is_supported(&self, event_type: u8) -> bool {
+        self.0[(event_type / 8) as usize] & (0x1 << (event_type % 8)) != 0
+    }
+
+    /// Supported event types in ascending order. Bits without a known event type are skipped.
+    pub fn iter(&self) -> impl Iterator<Item = EventType> + '_ {
+        (0..=u8::MAX)
+            .filter(|&event_type| self.is_supported(event_type))
+            .filter_map(|event_type| EventType::try_from(event_type).ok())
+    }
+
+    pub fn contains_all(&self, types: &[EventType]) -> bool {
+        types
+            .iter()
+            .all(|&event_type| self.is_supported(event_type.into()))
+    }
+}
+
 pub const SMART_HEALTH: u8 = 0x01;
 pub const FW_COMMIT: u8 = 0x02;
-pub const TIMESTAMP_CHANGE: u8 = 0x02;
-pub const POR: u8 = 0x03;
-pub const NVM_HW_ERROR: u8 = 0x04;
-pub const CHANGE_NAMESPACE: u8 = 0x05;
+pub const TIMESTAMP_CHANGE: u8 = 0x03;
+pub const POR: u8 = 0x04;
+pub const NVM_HW_ERROR: u8 = 0x05;
+pub const CHANGE_NAMESPACE: u8 = 0x06;
 pub const FORMAT_NVM_START: u8 = 0x07;
 pub const FORMAT_NVM_COMPLETE: u8 = 0x08;
 pub const SANITIZE_START: u8 = 0x09;
@@ -112,14 +808,48 @@ pub const THERMAL_EXCURSION: u8 = 0x0d;
 pub const VENDOR_SPECIFC: u8 = 0xde;
 pub const TCG_DEFINED: u8 = 0xdf;
 
-#[derive(Debug, PartialEq, Eq, Default)]
+#[derive(Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Timestamp {
+    #[cfg_attr(feature = "serde", serde(with = "duration_millis"))]
     ms: Duration,
     origin: TimestampOrigin,
     synch: TimestampSynch,
 }
 
+impl Timestamp {
+    pub fn millis(&self) -> u64 {
+        self.ms.as_millis() as u64
+    }
+
+    /// Compares every field, whereas `==` and `Ord` only consider the time.
+    pub fn equivalent_to(&self, other: &Timestamp) -> bool {
+        self.ms == other.ms && self.origin == other.origin && self.synch == other.synch
+    }
+}
+
+impl PartialEq for Timestamp {
+    fn eq(&self, other: &Self) -> bool {
+        self.ms == other.ms
+    }
+}
+
+impl Eq for Timestamp {}
+
+impl PartialOrd for Timestamp {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Timestamp {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.ms.cmp(&other.ms)
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TimestampOrigin {
     #[default]
     Reset,
@@ -138,6 +868,7 @@ impl From<u8> for TimestampOrigin {
 }
 
 #[derive(Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TimestampSynch {
     #[default]
     Continuous,
@@ -155,7 +886,24 @@ impl From<u8> for TimestampSynch {
     }
 }
 
-fn parse_ms(input: &[u8]) -> IResult<&[u8], u64> {
+// Serializes a `Duration` as whole milliseconds, which is what the log stores
+#[cfg(feature = "serde")]
+mod duration_millis {
+    use serde::{Deserialize, Deserializer, Serializer};
+    use std::time::Duration;
+
+    pub fn serialize<S: Serializer>(ms: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u64(ms.as_millis() as u64)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Duration, D::Error> {
+        u64::deserialize(deserializer).map(Duration::from_millis)
+    }
+}
+
+// Only public for the fuzz target, not part of the API
+#[doc(hidden)]
+pub fn parse_ms(input: &[u8]) -> IResult<&[u8], u64> {
     let (input, ms) = take(6u8)(input)?;
     let ms = [ms[0], ms[1], ms[2], ms[3], ms[4], ms[5], 0, 0];
     IResult::Ok((input, u64::from_le_bytes(ms)))
@@ -189,12 +937,665 @@ fn parse_timestamp(input: &[u8]) -> IResult<&[u8], Timestamp> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::pel::test_helpers::{event_at, record};
+
+    #[test]
+    fn test_log_size() {
+        let pel = Pel {
+            len: MINIMUM_LOG_SIZE,
+            header_len: 512,
+            ..Default::default()
+        };
+        assert_eq!(pel.total_log_size_bytes(), 512);
+        assert_eq!(pel.events_section_size_bytes(), Some(0));
+
+        let pel = Pel {
+            len: 1024,
+            header_len: 512,
+            ..Default::default()
+        };
+        assert_eq!(pel.events_section_size_bytes(), Some(512));
+
+        let pel = Pel {
+            len: 0,
+            header_len: 512,
+            ..Default::default()
+        };
+        assert_eq!(pel.events_section_size_bytes(), None);
+    }
+
+    #[test]
+    fn test_vendor_specific_events_by_vid() {
+        let vendor_event = |vendor_id| {
+            Event::VendorSpecifc(record(
+                0,
+                VendorSpecifcInfo {
+                    vendor_id,
+                    data: vec![],
+                },
+            ))
+        };
+        let pel: Pel = [
+            vendor_event(0x144d),
+            vendor_event(0x1c5c),
+            Event::SmartHealth(record(0, SmartHealthInfo::default())),
+            vendor_event(0x144d),
+            vendor_event(0x144d),
+        ]
+        .into_iter()
+        .collect();
+
+        let by_vid = pel.vendor_specific_events_by_vid();
+        assert_eq!(by_vid.len(), 2);
+        assert_eq!(by_vid[&0x144d].len(), 3);
+        assert_eq!(by_vid[&0x1c5c].len(), 1);
+        assert!(Pel::default().vendor_specific_events_by_vid().is_empty());
+    }
+
+    #[test]
+    fn test_controller_id_range() {
+        assert_eq!(Pel::default().controller_id_range(), None);
+        assert_eq!(Pel::default().expected_controller_count(), 0);
+
+        let pel: Pel = [3, 1, 5, 3]
+            .into_iter()
+            .map(|ctrl_id| {
+                Event::Unknown(EventRecord {
+                    ctrl_id,
+                    ..record(0, UnknownInfo::default())
+                })
+            })
+            .collect();
+        assert_eq!(pel.controller_id_range(), Some((1, 5)));
+        assert_eq!(pel.expected_controller_count(), 5);
+    }
+
+    #[test]
+    fn test_assert_invariants() {
+        Pel::default().assert_invariants();
+
+        // the supported events bitmap is left to `validate`
+        let pel: Pel = [Event::SmartHealth(record(0, SmartHealthInfo::default()))]
+            .into_iter()
+            .collect();
+        pel.assert_invariants();
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "num_events")]
+    fn test_assert_invariants_num_events() {
+        let pel = Pel {
+            num_events: 2,
+            events: Some(vec![]),
+            ..Default::default()
+        };
+        pel.assert_invariants();
+    }
+
+    #[test]
+    fn test_subnqn() {
+        let mut pel = Pel {
+            subnqn: "nqn.2014-08.com.example:nvme:nvm-subsystem-sn-d78432 ".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(
+            pel.subnqn(),
+            "nqn.2014-08.com.example:nvme:nvm-subsystem-sn-d78432"
+        );
+        assert!(pel.subnqn_is_well_formed());
+
+        pel.subnqn = "nqn.2014-08.org.nvmexpress:uuid:f81d4fae-7dec-11d0-a765-00a0c91e6bf6".into();
+        assert!(pel.subnqn_is_well_formed());
+
+        pel.subnqn = "com.example:nvme".to_string();
+        assert!(!pel.subnqn_is_well_formed());
+
+        pel.subnqn = String::new();
+        assert!(!pel.subnqn_is_well_formed());
+    }
+
+    #[test]
+    fn test_send_sync() {
+        use static_assertions::assert_impl_all;
+
+        assert_impl_all!(Pel: Send, Sync);
+        assert_impl_all!(Event: Send, Sync);
+        assert_impl_all!(EventRecord<SmartHealthInfo>: Send, Sync);
+    }
+
+    #[test]
+    fn test_smart_events() {
+        let smart_event = |available_spare| {
+            Event::SmartHealth(record(
+                0,
+                SmartHealthInfo {
+                    available_spare,
+                    ..Default::default()
+                },
+            ))
+        };
+
+        let pel: Pel = [Event::Unknown(record(0, UnknownInfo::default()))]
+            .into_iter()
+            .collect();
+        assert!(pel.latest_smart_event().is_none());
+        assert!(pel.earliest_smart_event().is_none());
+
+        let pel: Pel = [
+            Event::Unknown(record(0, UnknownInfo::default())),
+            smart_event(100),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(pel.latest_smart_event().unwrap().info.available_spare, 100);
+        assert_eq!(
+            pel.earliest_smart_event().unwrap().info.available_spare,
+            100
+        );
+
+        let pel: Pel = [
+            smart_event(100),
+            Event::Unknown(record(0, UnknownInfo::default())),
+            smart_event(90),
+            smart_event(80),
+            Event::Unknown(record(0, UnknownInfo::default())),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(pel.latest_smart_event().unwrap().info.available_spare, 80);
+        assert_eq!(
+            pel.earliest_smart_event().unwrap().info.available_spare,
+            100
+        );
+    }
+
+    #[test]
+    fn test_events_count_by_hour() {
+        assert!(Pel::default().events_count_by_hour().is_empty());
+
+        const HOUR: u64 = 3_600_000;
+        let pel: Pel = [
+            0,
+            1,
+            HOUR - 1,
+            HOUR,
+            HOUR + 10,
+            2 * HOUR - 1,
+            HOUR + 500,
+            HOUR + 600,
+            5 * HOUR,
+            5 * HOUR + 1,
+            5 * HOUR + 2,
+            6 * HOUR - 1,
+        ]
+        .into_iter()
+        .map(event_at)
+        .collect();
+
+        let histogram = pel.events_count_by_hour();
+        assert_eq!(
+            histogram.into_iter().collect::<Vec<_>>(),
+            vec![(0, 3), (1, 5), (5, 4)]
+        );
+    }
+
+    #[test]
+    fn test_num_events_consistent() {
+        let pel: Pel = [event_at(0), event_at(1)].into_iter().collect();
+        assert!(pel.is_num_events_consistent());
+        assert_eq!(pel.reported_event_count(), 2);
+        assert_eq!(pel.actual_event_count(), 2);
+        assert_eq!(pel.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_num_events_consistent_empty() {
+        let pel = Pel::default();
+        assert!(pel.is_num_events_consistent());
+        assert_eq!(pel.actual_event_count(), 0);
+        assert_eq!(pel.validate(), Ok(()));
+
+        let pel = Pel {
+            events: Some(vec![]),
+            ..Default::default()
+        };
+        assert!(pel.is_num_events_consistent());
+        assert_eq!(pel.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_num_events_mismatch() {
+        let mut pel: Pel = [event_at(0), event_at(1)].into_iter().collect();
+        pel.num_events = 5;
+        assert!(!pel.is_num_events_consistent());
+        assert_eq!(pel.reported_event_count(), 5);
+        assert_eq!(pel.actual_event_count(), 2);
+        assert_eq!(
+            pel.validate(),
+            Err(ValidationError::EventCountMismatch {
+                reported: 5,
+                actual: 2
+            })
+        );
+    }
+
+    #[test]
+    fn test_validate_supported_events() {
+        let mut pel: Pel = [
+            event_at(0),
+            Event::SmartHealth(record(1, SmartHealthInfo::default())),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(
+            pel.validate(),
+            Err(ValidationError::UnsupportedEventType {
+                index: 1,
+                event_type: SMART_HEALTH
+            })
+        );
+
+        pel.supp_events.0[0] = 1 << SMART_HEALTH;
+        assert_eq!(pel.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_events_since_index() {
+        let pel: Pel = (0..5).map(event_at).collect();
+        let events = pel.events_since_index(2);
+        assert_eq!(events.len(), 3);
+        assert_eq!(events[0].timestamp().millis(), 2);
+        assert_eq!(pel.events_since_index(0).len(), 5);
+    }
+
+    #[test]
+    fn test_events_since_index_boundary() {
+        let pel: Pel = (0..5).map(event_at).collect();
+        let events = pel.events_since_index(4);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].timestamp().millis(), 4);
+        assert!(pel.events_since_index(5).is_empty());
+    }
+
+    #[test]
+    fn test_events_since_index_out_of_range() {
+        let pel: Pel = (0..5).map(event_at).collect();
+        assert!(pel.events_since_index(6).is_empty());
+        assert!(pel.events_since_index(u32::MAX).is_empty());
+        assert!(Pel::default().events_since_index(0).is_empty());
+    }
+
+    #[test]
+    fn test_index() {
+        let pel: Pel = (0..3).map(event_at).collect();
+        assert_eq!(pel[0].timestamp().millis(), 0);
+        assert_eq!(pel[2].timestamp().millis(), 2);
+        assert_eq!(pel.get(1).unwrap().timestamp().millis(), 1);
+        assert!(pel.get(3).is_none());
+        assert!(Pel::default().get(0).is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "out of bounds")]
+    fn test_index_out_of_bounds() {
+        let pel: Pel = (0..3).map(event_at).collect();
+        let _ = &pel[3];
+    }
+
+    #[test]
+    #[should_panic(expected = "events weren't parsed")]
+    fn test_index_headers_only() {
+        let _ = &Pel::default()[0];
+    }
+
+    #[test]
+    fn test_event_type_predicates() {
+        let standard: Vec<_> = (SMART_HEALTH..=THERMAL_EXCURSION)
+            .map(|code| EventType::try_from(code).unwrap())
+            .collect();
+        assert_eq!(standard.len(), 13);
+        for event_type in standard {
+            assert!(event_type.is_standard());
+            assert!(!event_type.is_vendor_defined());
+            assert!(!event_type.is_tcg_defined());
+        }
+
+        assert!(EventType::VendorSpecific.is_vendor_defined());
+        assert!(!EventType::VendorSpecific.is_standard());
+        assert!(EventType::TcgDefined.is_tcg_defined());
+        assert!(!EventType::TcgDefined.is_standard());
+    }
+
+    #[test]
+    fn test_event_type_codes() {
+        for code in 0..=u8::MAX {
+            match EventType::try_from(code) {
+                Ok(event_type) => assert_eq!(u8::from(event_type), code),
+                Err(value) => assert_eq!(value, code),
+            }
+        }
+        assert_eq!(EventType::try_from(0x00), Err(0x00));
+        assert_eq!(EventType::try_from(0x0e), Err(0x0e));
+        assert_eq!(EventType::try_from(0xde), Ok(EventType::VendorSpecific));
+    }
+
+    #[test]
+    fn test_count_events() {
+        let ts = |ms| Timestamp {
+            ms: Duration::from_millis(ms),
+            ..Default::default()
+        };
+        let pel: Pel = (0..10).map(|i| event_at(i * 1000)).collect();
+
+        assert_eq!(pel.count_events_before(&ts(4500)), 5);
+        assert_eq!(pel.count_events_after(&ts(4500)), 5);
+        assert_eq!(pel.count_events_before(&ts(5000)), 5);
+        assert_eq!(pel.count_events_after(&ts(5000)), 4);
+        assert_eq!(pel.count_events_between(&ts(0), &ts(4500)), 5);
+        assert_eq!(pel.count_events_between(&ts(2000), &ts(5000)), 3);
+        assert_eq!(pel.count_events_between(&ts(5000), &ts(2000)), 0);
+
+        let pel = Pel::default();
+        assert_eq!(pel.count_events_before(&ts(4500)), 0);
+        assert_eq!(pel.count_events_after(&ts(4500)), 0);
+        assert_eq!(pel.count_events_between(&ts(0), &ts(4500)), 0);
+    }
+
+    #[test]
+    fn test_is_empty() {
+        let pel = Pel::default();
+        assert!(pel.is_empty());
+        assert!(!pel.has_events());
+        assert_eq!(pel.len(), 0);
+
+        let pel = Pel {
+            events: Some(vec![]),
+            ..Default::default()
+        };
+        assert!(pel.is_empty());
+        assert!(!pel.has_events());
+        assert_eq!(pel.len(), 0);
+
+        let pel: Pel = (0..3).map(event_at).collect();
+        assert!(!pel.is_empty());
+        assert!(pel.has_events());
+        assert_eq!(pel.len(), 3);
+    }
+
+    #[test]
+    fn test_latest_por_event() {
+        let pel: Pel = (0..10)
+            .map(|i| match i {
+                2 | 7 => Event::Por(EventRecord {
+                    ctrl_id: i,
+                    ..record(0, PorInfo::default())
+                }),
+                _ => event_at(i as u64),
+            })
+            .collect();
+
+        let (i, event) = pel.latest_por_event().unwrap();
+        assert_eq!(i, 7);
+        assert_eq!(event.ctrl_id, 7);
+
+        let session = pel.current_session_events();
+        assert_eq!(session.len(), 2);
+        assert_eq!(session[0].timestamp().millis(), 8);
+        assert_eq!(session[1].timestamp().millis(), 9);
+    }
+
+    #[test]
+    fn test_latest_por_event_none() {
+        let pel: Pel = (0..3).map(event_at).collect();
+        assert!(pel.latest_por_event().is_none());
+        assert_eq!(pel.current_session_events().len(), 3);
+
+        let pel = Pel::default();
+        assert!(pel.latest_por_event().is_none());
+        assert!(pel.current_session_events().is_empty());
+    }
+
+    #[test]
+    fn test_vid_name() {
+        let mut pel = Pel {
+            vid: 0x144d,
+            ..Default::default()
+        };
+        assert_eq!(pel.vid_name(), Some("Samsung"));
+
+        pel.vid = 0x1987;
+        assert_eq!(pel.vid_name(), Some("Phison"));
+
+        pel.vid = 0xffff;
+        assert_eq!(pel.vid_name(), None);
+    }
+
+    #[test]
+    fn test_fw_revision() {
+        let fw_commit = |previous: &str, new: &str, commit_status, result| {
+            Event::FwCommit(record(
+                0,
+                FwCommitInfo {
+                    previous_revision: previous.to_string(),
+                    new_revision: new.to_string(),
+                    commit_status,
+                    commit_result: FwCommitResult(result),
+                    ..Default::default()
+                },
+            ))
+        };
+
+        let pel: Pel = [event_at(0)].into_iter().collect();
+        assert_eq!(pel.latest_fw_revision(), None);
+        assert_eq!(pel.initial_fw_revision(), None);
+
+        let pel: Pel = [
+            fw_commit("1.0.0", "1.5.0", 0, 0),
+            event_at(1),
+            fw_commit("1.5.0", "1.4.0", 0, 0),
+            fw_commit("1.4.0", "2.0.0", 0x0b, 0),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(pel.latest_fw_revision(), Some("1.4.0".to_string()));
+        assert_eq!(pel.initial_fw_revision(), Some("1.0.0".to_string()));
+
+        let pel: Pel = [fw_commit("1.0.0", "2.0.0", 0x0b, 0)].into_iter().collect();
+        assert_eq!(pel.latest_fw_revision(), None);
+        assert_eq!(pel.initial_fw_revision(), Some("1.0.0".to_string()));
+
+        // the vendor assigned result doesn't say whether the command succeeded
+        let pel: Pel = [fw_commit("1.0.0", "2.0.0", 0, 0x1234)]
+            .into_iter()
+            .collect();
+        assert_eq!(pel.latest_fw_revision(), Some("2.0.0".to_string()));
+    }
+
+    #[test]
+    fn test_events_per_power_on_hour() {
+        let mut pel: Pel = (0..100).map(event_at).collect();
+        pel.power_on_hours = 50;
+        pel.power_cycle_count = 4;
+        assert_eq!(pel.events_per_power_on_hour(), 2.0);
+        assert_eq!(pel.events_per_power_cycle(), 25.0);
+
+        pel.power_on_hours = 0;
+        pel.power_cycle_count = 0;
+        assert_eq!(pel.events_per_power_on_hour(), 100.0);
+        assert_eq!(pel.events_per_power_cycle(), 100.0);
+
+        assert_eq!(Pel::default().events_per_power_on_hour(), 0.0);
+    }
+
+    #[test]
+    fn test_pel_stats() {
+        let pel: Pel = [
+            event_at(30),
+            Event::SmartHealth(record(0, SmartHealthInfo::default())),
+            event_at(10),
+            Event::Por(record(0, PorInfo::default())),
+            Event::SmartHealth(record(0, SmartHealthInfo::default())),
+        ]
+        .into_iter()
+        .collect();
+
+        let stats: PelStats = pel.into();
+        assert_eq!(stats.num_events, 5);
+        assert_eq!(
+            stats.events_by_type,
+            BTreeMap::from([(EventType::SmartHealth, 2), (EventType::Por, 1)])
+        );
+        assert_eq!(stats.unknown_events, 2);
+        assert_eq!(stats.first_event_ms, Some(0));
+        assert_eq!(stats.last_event_ms, Some(30));
+
+        assert_eq!(PelStats::from(Pel::default()), PelStats::default());
+
+        // headers only events are all `Event::Unknown`, but are counted by their type code
+        let input = test_helpers::make_full_pel(&[
+            test_helpers::make_smart_event_bytes(1, 1, 1000),
+            test_helpers::make_event_bytes(0x42, 1, 1, 2000, &[]),
+        ]);
+        let pel = parse_pel_headers_only(&input).unwrap();
+        let events = pel.events.as_deref().unwrap();
+        assert_eq!(events[0].event_type(), None);
+        assert_eq!(events[0].type_code(), SMART_HEALTH);
+        assert_eq!(events[1].type_code(), 0x42);
+        let stats = PelStats::from(&pel);
+        assert_eq!(
+            stats.events_by_type,
+            BTreeMap::from([(EventType::SmartHealth, 1)])
+        );
+        assert_eq!(stats.unknown_events, 1);
+    }
+
+    #[test]
+    fn test_events_with_short_duration() {
+        let pel: Pel = [0, 100, 5000, 5050].into_iter().map(event_at).collect();
+        let pairs: Vec<_> = pel
+            .events_with_short_duration(200)
+            .into_iter()
+            .map(|(a, b)| (a.timestamp().millis(), b.timestamp().millis()))
+            .collect();
+        assert_eq!(pairs, vec![(0, 100), (5000, 5050)]);
+
+        assert_eq!(pel.events_with_short_duration(10_000).len(), 3);
+        assert!(pel.events_with_short_duration(10).is_empty());
+        assert!(Pel::default().events_with_short_duration(200).is_empty());
+    }
+
+    #[test]
+    fn test_ssvid_name_oem() {
+        let pel = Pel {
+            vid: 0x144d,
+            ssvid: 0x1028,
+            ..Default::default()
+        };
+        assert_eq!(pel.ssvid_name(), Some("Dell"));
+        assert!(pel.is_oem_device());
+    }
+
+    #[test]
+    fn test_ssvid_name_non_oem() {
+        let pel = Pel {
+            vid: 0x144d,
+            ssvid: 0x144d,
+            ..Default::default()
+        };
+        assert_eq!(pel.ssvid_name(), None);
+        assert!(!pel.is_oem_device());
+    }
+
+    #[test]
+    fn test_event_age_hours() {
+        let mut pel = Pel {
+            power_on_hours: 10,
+            ..Default::default()
+        };
+        assert_eq!(pel.oldest_event_age_hours(), None);
+        assert_eq!(pel.newest_event_age_hours(), None);
+
+        pel.extend([3_600_000, 5_400_000, 7_200_000].map(event_at));
+        assert_eq!(pel.first_event_timestamp().unwrap().millis(), 3_600_000);
+        assert_eq!(pel.last_event_timestamp().unwrap().millis(), 7_200_000);
+        assert_eq!(pel.oldest_event_age_hours(), Some(9.0));
+        assert_eq!(pel.newest_event_age_hours(), Some(8.0));
+    }
+
+    #[test]
+    fn test_try_from_event() {
+        let event = Event::SmartHealth(EventRecord {
+            ctrl_id: 3,
+            ..record(0, SmartHealthInfo::default())
+        });
+        let smart_event = SmartHealthEvent::try_from(event).unwrap();
+        assert_eq!(smart_event.ctrl_id, 3);
+
+        let event = Event::FwCommit(record(0, FwCommitInfo::default()));
+        let event = SmartHealthEvent::try_from(event).unwrap_err();
+        assert!(matches!(event, Event::FwCommit(_)));
+        assert!(FwCommitEvent::try_from(event).is_ok());
+    }
+
+    #[test]
+    fn test_events_after_format_nvm() {
+        let format_complete =
+            |nsid| Event::FormatNvmComplete(record(0, FormatNvmCompleteInfo { nsid }));
+        let pel: Pel = (0..15)
+            .map(|i| match i {
+                3 | 10 => format_complete(1),
+                12 => format_complete(2),
+                _ => event_at(i),
+            })
+            .collect();
+
+        let events = pel.events_after_format_nvm(1);
+        assert_eq!(events.len(), 4);
+        assert!(matches!(events[1], Event::FormatNvmComplete(_)));
+        assert_eq!(events[3].timestamp().millis(), 14);
+
+        assert_eq!(pel.events_after_format_nvm(2).len(), 2);
+        assert_eq!(pel.events_after_format_nvm(3).len(), 15);
+        assert!(Pel::default().events_after_format_nvm(1).is_empty());
+    }
+
+    #[test]
+    fn test_vendor_name() {
+        let mut pel = Pel {
+            vid: 0x144d,
+            serial_num: "S1234".to_string(),
+            model_num: "PM9A3".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(pel.vendor_name(), "Samsung PM9A3 (SN: S1234)");
+        assert_eq!(pel.device_id(), "S1234@PM9A3");
+
+        pel.vid = 0xabcd;
+        assert_eq!(pel.vendor_name(), "VID:0xABCD PM9A3 (SN: S1234)");
+    }
+
+    #[test]
+    fn test_collect_events() {
+        let events = vec![
+            Event::SmartHealth(record(0, SmartHealthInfo::default())),
+            Event::FwCommit(record(0, FwCommitInfo::default())),
+            Event::Unknown(record(0, UnknownInfo::default())),
+        ];
+        let mut pel: Pel = events.into_iter().collect();
+        assert_eq!(pel.num_events, 3);
+        assert_eq!(pel.events.as_ref().unwrap().len(), 3);
+
+        pel.extend([Event::Por(record(0, PorInfo::default()))]);
+        assert_eq!(pel.num_events, 4);
+        assert!(matches!(pel.events.as_ref().unwrap()[3], Event::Por(_)));
+    }
 
     #[test]
     fn test_parse_ms() {
         let ms_le_bytes = [0u8; 6];
         let (remainder, parsed_ms) = parse_ms(&ms_le_bytes).unwrap();
-        assert_eq!(remainder, &[]);
+        assert_eq!(remainder, &[0u8; 0]);
         assert_eq!(parsed_ms, 0u64);
 
         let ms_le_bytes = [0, 0xa, 0xb, 0xc, 0xd, 0xe, 0xf];
@@ -209,43 +1610,267 @@ mod tests {
         assert_eq!(parsed_ms, 0x000a0b0c0d0e0f);
     }
 
+    #[test]
+    fn test_parse_ms_max() {
+        let (remainder, parsed_ms) = parse_ms(&[0xff; 6]).unwrap();
+        assert_eq!(remainder, &[0u8; 0]);
+        assert_eq!(parsed_ms, 0x0000_ffff_ffff_ffff);
+    }
+
+    #[test]
+    fn test_timestamp_eq() {
+        let a = Timestamp {
+            ms: Duration::from_millis(10),
+            origin: TimestampOrigin::Reset,
+            synch: TimestampSynch::Continuous,
+        };
+        let b = Timestamp {
+            ms: Duration::from_millis(10),
+            origin: TimestampOrigin::SetFeature,
+            synch: TimestampSynch::Skipped,
+        };
+        let c = Timestamp {
+            ms: Duration::from_millis(20),
+            ..Default::default()
+        };
+        assert_eq!(a, b);
+        assert_eq!(a.cmp(&b), std::cmp::Ordering::Equal);
+        assert!(!a.equivalent_to(&b));
+        assert!(a.equivalent_to(&a));
+        assert!(a < c);
+        assert_ne!(a, c);
+    }
+
     #[test]
     fn test_parse_timestamp() {
         let (remainder, parsed_timestamp) = parse_timestamp(&[0u8; 8]).unwrap();
         assert_eq!(remainder.len(), 0);
-        assert_eq!(
-            parsed_timestamp,
-            Timestamp {
-                ms: Duration::from_millis(0),
-                synch: TimestampSynch::Continuous,
-                origin: TimestampOrigin::Reset,
-            }
-        );
+        assert!(parsed_timestamp.equivalent_to(&Timestamp {
+            ms: Duration::from_millis(0),
+            synch: TimestampSynch::Continuous,
+            origin: TimestampOrigin::Reset,
+        }));
 
         let (remainder, parsed_timestamp) =
             parse_timestamp(&[0x0, 0x11, 0x22, 0x33, 0x44, 0x55, 0b00000011, 0xff]).unwrap();
         assert_eq!(remainder.len(), 0);
-        assert_eq!(
-            parsed_timestamp,
-            Timestamp {
-                ms: Duration::from_millis(0x554433221100),
-                synch: TimestampSynch::Skipped,
-                origin: TimestampOrigin::SetFeature,
-            }
-        );
+        assert!(parsed_timestamp.equivalent_to(&Timestamp {
+            ms: Duration::from_millis(0x554433221100),
+            synch: TimestampSynch::Skipped,
+            origin: TimestampOrigin::SetFeature,
+        }));
 
         let (remainder, parsed_timestamp) =
             parse_timestamp(&[0x0, 0x11, 0x22, 0x33, 0x44, 0x55, 0b00000101, 0xff]).unwrap();
         assert_eq!(remainder.len(), 0);
+        assert!(parsed_timestamp.equivalent_to(&Timestamp {
+            ms: Duration::from_millis(0x554433221100),
+            synch: TimestampSynch::Skipped,
+            origin: TimestampOrigin::Unknown(2u8),
+        }));
+    }
+
+    #[test]
+    fn test_parse_pel() {
+        use self::test_helpers::*;
+
+        let mut smart = [0u8; SMART_EVENT_LEN];
+        // 05 - percentage used
+        smart[5] = 42;
+        let input = make_full_pel(&[
+            make_event_bytes(SMART_HEALTH, 1, 1, 1000, &smart),
+            make_fw_commit_event_bytes(1, 1, 2000, "1.0", "2.0"),
+            make_event_bytes(POR, 1, 2, 3000, &[0u8; 16]),
+            make_event_bytes(0x70, 1, 2, 4000, &[0u8; 4]),
+        ]);
+
+        let pel = parse_pel(&input).unwrap();
+        assert_eq!(pel.num_events, 4);
+        assert_eq!(pel.len, input.len() as u64);
+        assert_eq!(pel.header_len, 512);
+
+        let events = pel.events.as_deref().unwrap();
+        assert_eq!(events.len(), 4);
+        match &events[0] {
+            Event::SmartHealth(record) => assert_eq!(record.info.percentage_used, 42),
+            event => panic!("expected a SMART event, got {event:?}"),
+        }
+        assert!(matches!(events[1], Event::FwCommit(_)));
+        assert!(matches!(events[2], Event::Por(_)));
+        assert!(matches!(events[3], Event::Unknown(_)));
+        assert_eq!(events[2].ctrl_id(), 2);
+        assert_eq!(events[3].timestamp().millis(), 4000);
+    }
+
+    #[test]
+    fn test_parse_pel_headers_only() {
+        use self::test_helpers::*;
+
+        let input = make_full_pel(&[
+            make_smart_event_bytes(1, 1, 1000),
+            make_fw_commit_event_bytes(1, 1, 2000, "1.0", "2.0"),
+            make_event_bytes(POR, 1, 2, 3000, &[0u8; 16]),
+        ]);
+
+        let pel = parse_pel_headers_only(&input).unwrap();
+        let events = pel.events.as_deref().unwrap();
+        assert_eq!(pel.num_events as usize, events.len());
+        assert!(events
+            .iter()
+            .all(|event| matches!(event, Event::Unknown(_))));
+        let timestamps: Vec<_> = events.iter().map(|e| e.timestamp().millis()).collect();
+        assert_eq!(timestamps, [1000, 2000, 3000]);
+        assert_eq!(*record_field!(&events[0], len), 536);
+    }
+
+    #[test]
+    fn test_parse_pel_no_events() {
+        let input = test_helpers::make_minimal_log_header();
+        let pel = parse_pel(&input).unwrap();
+        assert_eq!(pel.num_events, 0);
+        assert!(pel.events.is_some_and(|events| events.is_empty()));
+    }
+
+    #[test]
+    fn test_parse_pel_truncated() {
+        let mut input =
+            test_helpers::make_full_pel(&[test_helpers::make_smart_event_bytes(1, 1, 1000)]);
+        input.truncate(input.len() - 100);
         assert_eq!(
-            parsed_timestamp,
-            Timestamp {
-                ms: Duration::from_millis(0x554433221100),
-                synch: TimestampSynch::Skipped,
-                origin: TimestampOrigin::Unknown(2u8),
+            parse_pel(&input).unwrap_err(),
+            PelError::EventBodyTooShort {
+                index: 0,
+                offset: 512,
+                event_type: SMART_HEALTH,
+                declared_len: 536,
+                available: 436
             }
         );
+
+        // event header claims more data than the log has
+        input.truncate(test_helpers::LOG_HEADER_LEN + EVENT_HEADER_LEN);
+        assert!(matches!(
+            parse_pel(&input),
+            Err(PelError::EventBodyTooShort { available: 24, .. })
+        ));
+
+        input.truncate(100);
+        assert_eq!(
+            parse_pel(&input).unwrap_err(),
+            PelError::InsufficientData { needed: 412 }
+        );
     }
-}
 
+    #[test]
+    fn test_parse_pel_invalid_log_id() {
+        let mut input = test_helpers::make_minimal_log_header();
+        input[0] = 0x02;
+        assert_eq!(
+            parse_pel(&input).unwrap_err(),
+            PelError::InvalidLogId {
+                expected: 0x0d,
+                got: 0x02
+            }
+        );
+    }
+
+    const ALL_EVENT_TYPES: [EventType; 15] = [
+        EventType::SmartHealth,
+        EventType::FwCommit,
+        EventType::TimestampChange,
+        EventType::Por,
+        EventType::NvmHwError,
+        EventType::ChangeNamespace,
+        EventType::FormatNvmStart,
+        EventType::FormatNvmComplete,
+        EventType::SanitizeStart,
+        EventType::SanitizeComplete,
+        EventType::SetFeature,
+        EventType::TelementryLogCreated,
+        EventType::ThermalExcursion,
+        EventType::VendorSpecific,
+        EventType::TcgDefined,
+    ];
+
+    fn bitmap_of(types: &[u8]) -> SuppEventsBitmap {
+        let mut bitmap = [0u8; 32];
+        for &event_type in types {
+            bitmap[(event_type / 8) as usize] |= 1 << (event_type % 8);
+        }
+        SuppEventsBitmap(bitmap)
+    }
+
+    #[test]
+    fn test_supp_events_is_supported() {
+        for event_type in ALL_EVENT_TYPES {
+            let raw = u8::from(event_type);
+            let bitmap = bitmap_of(&[raw]);
+            for other in 0..=u8::MAX {
+                assert_eq!(
+                    bitmap.is_supported(other),
+                    other == raw,
+                    "{raw:#04x} {other:#04x}"
+                );
+            }
+        }
+
+        // vendor specific and TCG live in byte 27
+        let bitmap = bitmap_of(&[VENDOR_SPECIFC, TCG_DEFINED]);
+        assert_eq!(bitmap.0[27], 0b1100_0000);
+        assert!(bitmap.is_supported(0xde));
+        assert!(bitmap.is_supported(0xdf));
+        assert!(!bitmap.is_supported(0xe0));
+    }
+
+    #[test]
+    fn test_supp_events_iter() {
+        assert_eq!(SuppEventsBitmap::default().iter().count(), 0);
+
+        let all: Vec<u8> = ALL_EVENT_TYPES.iter().map(|&t| t.into()).collect();
+        let bitmap = bitmap_of(&all);
+        assert_eq!(bitmap.iter().collect::<Vec<_>>(), ALL_EVENT_TYPES);
 
+        // unknown bits are skipped
+        let bitmap = bitmap_of(&[TCG_DEFINED, 0x70, SMART_HEALTH, 0xff]);
+        assert_eq!(
+            bitmap.iter().collect::<Vec<_>>(),
+            [EventType::SmartHealth, EventType::TcgDefined]
+        );
+    }
+
+    #[test]
+    fn test_supp_events_contains_all() {
+        let bitmap = bitmap_of(&[SMART_HEALTH, POR, VENDOR_SPECIFC]);
+        assert!(bitmap.contains_all(&[]));
+        assert!(bitmap.contains_all(&[EventType::Por, EventType::VendorSpecific]));
+        assert!(!bitmap.contains_all(&[EventType::Por, EventType::TcgDefined]));
+
+        let all: Vec<u8> = ALL_EVENT_TYPES.iter().map(|&t| t.into()).collect();
+        assert!(bitmap_of(&all).contains_all(&ALL_EVENT_TYPES));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip() {
+        use self::test_helpers::*;
+
+        let input = make_full_pel(&[
+            make_smart_event_bytes(1, 1, 1000),
+            make_fw_commit_event_bytes(1, 1, 2000, "1.0", "2.0"),
+            make_event_bytes(POR, 1, 2, 3000, &[0u8; 16]),
+        ]);
+        let pel = parse_pel(&input).unwrap();
+
+        let json = serde_json::to_string(&pel).unwrap();
+        let round_tripped: Pel = serde_json::from_str(&json).unwrap();
+        assert_eq!(serde_json::to_string(&round_tripped).unwrap(), json);
+        assert_eq!(round_tripped.num_events, 3);
+        assert_eq!(round_tripped[2].timestamp().millis(), 3000);
+
+        // timestamps are plain milliseconds and records are inline
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        let por = &value["events"][2]["Por"];
+        assert_eq!(por["timestamp"]["ms"], 3000);
+        assert!(por["info"].is_object());
+    }
+}
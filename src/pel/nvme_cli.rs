@@ -0,0 +1,153 @@
+use serde_json::{json, Value};
+
+use super::{Event, Pel, ReportingContext, PEL_LOG_ID};
+
+impl Pel {
+    /// Builds the JSON produced by `nvme persistent-event-log --output-format json`.
+    ///
+    /// Field names follow nvme-cli rather than this crate:
+    ///
+    /// | nvme-cli              | `Pel` / `EventRecord` |
+    /// |-----------------------|-----------------------|
+    /// | `log_id`              | `PEL_LOG_ID`          |
+    /// | `total_num_of_events` | `num_events`          |
+    /// | `total_log_len`       | `len`                 |
+    /// | `log_revision`        | `revision`            |
+    /// | `log_header_len`      | `header_len`          |
+    /// | `timestamp`           | `timestamp` (ms)      |
+    /// | `power_on_hours`      | `power_on_hours`      |
+    /// | `power_cycle_count`   | `power_cycle_count`   |
+    /// | `pci_vid`             | `vid`                 |
+    /// | `pci_ssvid`           | `ssvid`               |
+    /// | `sn`                  | `serial_num`          |
+    /// | `mn`                  | `model_num`           |
+    /// | `subnqn`              | `subnqn`              |
+    /// | `gen_number`          | `generation`          |
+    /// | `rci`                 | `reporting_context`   |
+    /// | `event_type`          | `Event::type_code`    |
+    /// | `event_type_rev`      | `revision`            |
+    /// | `event_header_len`    | `header_len` - 3      |
+    /// | `ctrl_id`             | `ctrl_id`             |
+    /// | `event_time_stamp`    | `timestamp` (ms)      |
+    /// | `vu_info_len`         | `vendor_info_len`     |
+    /// | `event_len`           | `len` - `header_len`  |
+    ///
+    /// nvme-cli prints 128-bit counters as strings, and so does this.
+    pub fn generate_nvme_cli_compatible_json(&self) -> Value {
+        let rci = match self.reporting_context {
+            None | Some(ReportingContext::DoesNotExist) => 0,
+            Some(ReportingContext::NVMPort(port)) => 1 << 18 | 0b01 << 16 | port as u32,
+            Some(ReportingContext::MiPort(port)) => 1 << 18 | 0b10 << 16 | port as u32,
+        };
+        let events: Vec<Value> = self
+            .events
+            .iter()
+            .flatten()
+            .enumerate()
+            .map(|(i, event)| event_json(i, event))
+            .collect();
+
+        json!({
+            "log_id": PEL_LOG_ID,
+            "total_num_of_events": self.num_events,
+            "total_log_len": self.len,
+            "log_revision": self.revision,
+            "log_header_len": self.header_len,
+            "timestamp": self.timestamp.millis(),
+            "power_on_hours": self.power_on_hours.to_string(),
+            "power_cycle_count": self.power_cycle_count,
+            "pci_vid": self.vid,
+            "pci_ssvid": self.ssvid,
+            "sn": self.serial_num,
+            "mn": self.model_num,
+            "subnqn": self.subnqn,
+            "gen_number": self.generation.unwrap_or_default(),
+            "rci": rci,
+            "events": events,
+        })
+    }
+}
+
+fn event_json(number: usize, event: &Event) -> Value {
+    let header_len = *record_field!(event, header_len);
+    // nvme-cli prints EHL and EL as they are in the event header, neither counts the header
+    json!({
+        "event_number": number,
+        "event_type": event.type_code(),
+        "event_type_rev": record_field!(event, revision),
+        "event_header_len": header_len.saturating_sub(3),
+        "ctrl_id": event.ctrl_id(),
+        "event_time_stamp": event.timestamp().millis(),
+        "vu_info_len": record_field!(event, vendor_info_len),
+        "event_len": record_field!(event, len).saturating_sub(header_len as u16),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pel::test_helpers::{
+        make_full_pel, make_smart_event_bytes, record, SMART_EVENT_LEN,
+    };
+    use crate::pel::{parse_pel_headers_only, EventRecord, PorInfo, SMART_HEALTH};
+
+    #[test]
+    fn test_nvme_cli_json() {
+        let mut pel: Pel = [Event::Por(EventRecord {
+            ctrl_id: 2,
+            len: 48,
+            ..record(0, PorInfo::default())
+        })]
+        .into_iter()
+        .collect();
+        pel.serial_num = "SN1".to_string();
+        pel.power_on_hours = u128::MAX;
+        pel.reporting_context = Some(ReportingContext::MiPort(3));
+
+        let value = pel.generate_nvme_cli_compatible_json();
+        let value: Value = serde_json::from_str(&value.to_string()).unwrap();
+        for key in [
+            "log_id",
+            "total_num_of_events",
+            "total_log_len",
+            "log_revision",
+            "log_header_len",
+            "timestamp",
+            "power_on_hours",
+            "power_cycle_count",
+            "pci_vid",
+            "pci_ssvid",
+            "sn",
+            "mn",
+            "subnqn",
+            "gen_number",
+            "rci",
+        ] {
+            assert!(value.get(key).is_some(), "missing {key}");
+        }
+        assert_eq!(value["total_num_of_events"], 1);
+        assert_eq!(value["sn"], "SN1");
+        assert_eq!(value["power_on_hours"], u128::MAX.to_string());
+        assert_eq!(value["rci"], 0x60003);
+
+        assert_eq!(value["log_id"], 0x0d);
+
+        let event = &value["events"][0];
+        assert_eq!(event["event_type"], 0x04);
+        assert_eq!(event["ctrl_id"], 2);
+        // EHL and EL, without the 24 byte header
+        assert_eq!(event["event_header_len"], 21);
+        assert_eq!(event["event_len"], 24);
+    }
+
+    #[test]
+    fn test_nvme_cli_json_headers_only() {
+        let input = make_full_pel(&[make_smart_event_bytes(1, 1, 1000)]);
+        let pel = parse_pel_headers_only(&input).unwrap();
+
+        let value = pel.generate_nvme_cli_compatible_json();
+        let event = &value["events"][0];
+        assert_eq!(event["event_type"], SMART_HEALTH);
+        assert_eq!(event["event_len"], SMART_EVENT_LEN);
+    }
+}
@@ -1,13 +1,13 @@
 use nom::{
-    bits,
     bytes::complete::take,
-    combinator::{map, peek},
+    error::{Error, ErrorKind},
+    multi::{count, fill},
     number::complete::{le_u128, le_u16, le_u32, le_u64, le_u8},
-    sequence::{preceded, terminated, tuple},
+    sequence::terminated,
     IResult,
 };
 
-use super::{parse_timestamp, Event, EventType, Pel, SuppEventsBitmap, Timestamp};
+use super::*;
 
 pub fn parse_log_header(input: &[u8]) -> IResult<&[u8], Pel> {
     // 00 - log id (always going to be 0Dh)
@@ -38,12 +38,15 @@ pub fn parse_log_header(input: &[u8]) -> IResult<&[u8], Pel> {
     // 115:76 - pci subsystem vendor id (SSVID)
     let (input, model_num) = take(40usize)(input)?;
     // 371:116 - nvm subsystem nvme qualified name (SUBNQN)
-    // 479:372 - reserved
-    let (input, name) = terminated(take(256usize), take(108usize))(input)?;
+    let (input, subnqn) = take(256usize)(input)?;
+    // 373:372 - generation number (revision 2+)
+    let (input, generation) = le_u16(input)?;
+    // 377:374 - reporting context information (revision 2+)
+    // 479:378 - reserved
+    let (input, reporting_context) = terminated(le_u32, take(102usize))(input)?;
     // 511:480 - supported events bitmap
     let (input, supp_events) = take(32usize)(input)?;
 
-    let clean_str = |s: &[u8]| String::from_utf8_lossy(s).trim().replace('\0', "");
     IResult::Ok((
         input,
         Pel {
@@ -58,21 +61,28 @@ pub fn parse_log_header(input: &[u8]) -> IResult<&[u8], Pel> {
             ssvid,
             serial_num: clean_str(serial_num),
             model_num: clean_str(model_num),
-            name: clean_str(name),
+            subnqn: clean_str(subnqn),
             supp_events: SuppEventsBitmap(supp_events.try_into().unwrap()),
+            generation: (revision >= 2).then_some(generation),
+            reporting_context: (revision >= 2).then(|| ReportingContext::from(reporting_context)),
             ..Default::default()
         },
     ))
 }
 
-pub fn parse_event(input: &[u8], headers_only: bool) -> IResult<&[u8], Event> {
+// ASCII fields are space or NUL padded
+fn clean_str(s: &[u8]) -> String {
+    String::from_utf8_lossy(s).trim().replace('\0', "")
+}
+
+pub fn parse_event_header(input: &[u8]) -> IResult<&[u8], (u8, EventRecord<()>)> {
     // 00 - event type
     let (input, event_type) = le_u8(input)?;
     // 01 - event type revision
     let (input, revision) = le_u8(input)?;
     // 02 - event header length (EHL)
     // 03 - reserved
-    let (input, header_len) = terminated(le_u8, le_u8)(input)?;
+    let (input, ehl) = terminated(le_u8, le_u8)(input)?;
     // 05:04 - controller id
     let (input, ctrl_id) = le_u16(input)?;
     // 13:06 - event timestamp
@@ -81,126 +91,882 @@ pub fn parse_event(input: &[u8], headers_only: bool) -> IResult<&[u8], Event> {
     // 21:20 - vendor specific information length (VSIL)
     let (input, vendor_info_len) = le_u16(input)?;
     // 23:22 - event length (EL)
-    let (input, event_len) = le_u16(input)?;
-
-    let (input, vendor_info) = parse_vendor_info(input, event_type, revision, vendor_info_len)?;
-
-    let length = event_len - header_len as u16;
-
-    let (input, event) = match event_type {
-        SMART_HEALTH => parse_smart_event(input, revision, length)?,
-        FW_COMMIT => parse_fw_commit_event(input, revision, length)?,
-        TIMESTAMP_CHANGE => parse_timestamp_change_event(input, revision, length)?,
-        POR => parse_por_event(input, revision, length)?,
-        NVM_HW_ERROR => parse_nvm_hw_error_event(input, revision, length)?,
-        CHANGE_NAMESPACE => parse_change_namespace_event(input, revision, length)?,
-        FORMAT_NVM_START => parse_format_nvm_start_event(input, revision, length)?,
-        FORMAT_NVM_COMPLETE => parse_format_nvm_complete_event(input, revision, length)?,
-        SANITIZE_START => parse_sanitize_start_event(input, revision, length)?,
-        SANITIZE_COMPLETE => parse_sanitize_complete_event(input, revision, length)?,
-        SET_FEATURE => parse_set_feature_event(input, revision, length)?,
-        TELEMENTRY_LOG_CREATED => parse_telementry_log_created_event(input, revision, length)?,
-        THERMAL_EXCURSION => parse_thermal_excursion_event(input, revision, length)?,
-        VENDOR_SPECIFC => parse_vendor_specific_event(input, revision, length)?,
-        TCG_DEFINED => parse_tcg_event(input, revision, length)?,
-        _ => parse_unknown_event(input, revision, length)?,
+    let (input, el) = le_u16(input)?;
+    // EHL + 2 - remaining header bytes, if any
+    let header_len = ehl.saturating_add(3);
+    let (input, _) = take((header_len as usize).saturating_sub(24))(input)?;
+
+    IResult::Ok((
+        input,
+        (
+            event_type,
+            EventRecord {
+                revision,
+                header_len,
+                ctrl_id,
+                timestamp,
+                vendor_info_len,
+                len: el.saturating_add(header_len as u16),
+                vendor_info: None,
+                info: Box::new(()),
+            },
+        ),
+    ))
+}
+
+// `vid` is the PCI vendor id from the log header, vendor specific events are tagged with it
+pub fn parse_event(input: &[u8], vid: u16, headers_only: bool) -> IResult<&[u8], Event> {
+    let (input, (event_type, header)) = parse_event_header(input)?;
+
+    // EL covers both the vendor specific information and the event data
+    let data_len = header.len - header.header_len as u16;
+    if input.len() < data_len as usize {
+        return Err(nom::Err::Error(Error::new(input, ErrorKind::Eof)));
+    }
+    if headers_only {
+        let (input, _) = take(data_len)(input)?;
+        let info = UnknownInfo {
+            event_type,
+            data: vec![],
+        };
+        return IResult::Ok((input, Event::Unknown(header.with_info(info))));
+    }
+    let length = match data_len.checked_sub(header.vendor_info_len) {
+        Some(length) => length,
+        None => return Err(nom::Err::Error(Error::new(input, ErrorKind::Verify))),
+    };
+
+    let (input, vendor_info) = parse_vendor_info(input, header.vendor_info_len)?;
+    let header = EventRecord {
+        vendor_info: vendor_info.map(|raw| VendorInfo {
+            raw: raw.to_vec(),
+            parsed: None,
+        }),
+        ..header
+    };
+
+    // Body parsers only see this event's data, so the next event always starts after it
+    let (input, body) = take(length)(input)?;
+    let (_, event) = match event_type {
+        SMART_HEALTH => parse_smart_event(body, header, length)?,
+        FW_COMMIT => parse_fw_commit_event(body, header, length)?,
+        TIMESTAMP_CHANGE => parse_timestamp_change_event(body, header, length)?,
+        POR => parse_por_event(body, header, length)?,
+        NVM_HW_ERROR => parse_nvm_hw_error_event(body, header, length)?,
+        CHANGE_NAMESPACE => parse_change_namespace_event(body, header, length)?,
+        FORMAT_NVM_START => parse_format_nvm_start_event(body, header, length)?,
+        FORMAT_NVM_COMPLETE => parse_format_nvm_complete_event(body, header, length)?,
+        SANITIZE_START => parse_sanitize_start_event(body, header, length)?,
+        SANITIZE_COMPLETE => parse_sanitize_complete_event(body, header, length)?,
+        SET_FEATURE => parse_set_feature_event(body, header, length)?,
+        TELEMENTRY_LOG_CREATED => parse_telementry_log_created_event(body, header, length)?,
+        THERMAL_EXCURSION => parse_thermal_excursion_event(body, header, length)?,
+        VENDOR_SPECIFC => parse_vendor_specific_event(body, header, length, vid)?,
+        TCG_DEFINED => parse_tcg_event(body, header, length)?,
+        _ => parse_unknown_event(body, header, length, event_type)?,
     };
 
     IResult::Ok((input, event))
 }
 
-fn parse_vendor_info(
+// The raw vendor specific information, if the event has any. `VendorInfoParser`s decode it
+fn parse_vendor_info(input: &[u8], length: u16) -> IResult<&[u8], Option<&[u8]>> {
+    let (input, vendor_info) = take(length)(input)?;
+    IResult::Ok((input, (length > 0).then_some(vendor_info)))
+}
+
+fn parse_unknown_event(
     input: &[u8],
-    event_type: u8,
-    revision: u8,
+    header: EventRecord<()>,
     length: u16,
+    event_type: u8,
 ) -> IResult<&[u8], Event> {
-    todo!()
+    let (input, data) = take(length)(input)?;
+    IResult::Ok((
+        input,
+        Event::Unknown(header.with_info(UnknownInfo {
+            event_type,
+            data: data.to_vec(),
+        })),
+    ))
 }
 
-fn parse_unknown_event(input: &[u8], revision: u8, length: u16) -> IResult<&[u8], Event> {
-    todo!()
+fn parse_tcg_event(input: &[u8], header: EventRecord<()>, length: u16) -> IResult<&[u8], Event> {
+    // TODO: decode the event data
+    let (input, data) = take(length)(input)?;
+    IResult::Ok((
+        input,
+        Event::TcgDefined(header.with_info(TcgDefinedInfo {
+            data: data.to_vec(),
+        })),
+    ))
 }
 
-fn parse_tcg_event(input: &[u8], revision: u8, length: u16) -> IResult<&[u8], Event> {
-    todo!()
+fn parse_vendor_specific_event(
+    input: &[u8],
+    header: EventRecord<()>,
+    length: u16,
+    vid: u16,
+) -> IResult<&[u8], Event> {
+    // the event data is entirely vendor defined
+    let (input, data) = take(length)(input)?;
+    IResult::Ok((
+        input,
+        Event::VendorSpecifc(header.with_info(VendorSpecifcInfo {
+            vendor_id: vid,
+            data: data.to_vec(),
+        })),
+    ))
 }
 
-fn parse_vendor_specific_event(input: &[u8], revision: u8, length: u16) -> IResult<&[u8], Event> {
-    todo!()
-}
+fn parse_thermal_excursion_event(
+    input: &[u8],
+    header: EventRecord<()>,
+    length: u16,
+) -> IResult<&[u8], Event> {
+    let (input, data) = take(length)(input)?;
+    // 00 - over temperature, cleared when the temperature fell under a threshold
+    let (data, over_temperature) = le_u8(data)?;
+    // 01 - threshold, in degrees Celsius
+    let (_, threshold) = le_u8(data)?;
 
-fn parse_thermal_excursion_event(input: &[u8], revision: u8, length: u16) -> IResult<&[u8], Event> {
-    todo!()
+    let over_threshold = over_temperature & 0x1 != 0;
+    IResult::Ok((
+        input,
+        Event::ThermalExcursion(header.with_info(ThermalExcursionInfo {
+            over_threshold,
+            under_threshold: !over_threshold,
+            threshold_kelvin: threshold as u16 + KELVIN_OFFSET,
+        })),
+    ))
 }
 
 fn parse_telementry_log_created_event(
     input: &[u8],
-    revision: u8,
+    header: EventRecord<()>,
     length: u16,
 ) -> IResult<&[u8], Event> {
-    todo!()
+    // TODO: decode the event data
+    let (input, data) = take(length)(input)?;
+    IResult::Ok((
+        input,
+        Event::TelementryLogCreated(header.with_info(TelementryLogCreatedInfo {
+            data: data.to_vec(),
+        })),
+    ))
 }
 
-fn parse_set_feature_event(input: &[u8], revision: u8, length: u16) -> IResult<&[u8], Event> {
-    todo!()
+fn parse_set_feature_event(
+    input: &[u8],
+    header: EventRecord<()>,
+    length: u16,
+) -> IResult<&[u8], Event> {
+    let (input, data) = take(length)(input)?;
+    // 03:00 - layout: 02:00 - command dword count, 31:16 - memory buffer count
+    let (data, layout) = le_u32(data)?;
+    // 07:04 - dword 0 of the logged command (CDW10): 07:00 - FID, 31 - SV
+    let (data, cdw10) = le_u32(data)?;
+    // the rest of the command dwords and the memory buffer
+    IResult::Ok((
+        input,
+        Event::SetFeature(header.with_info(SetFeatureInfo {
+            feature: Feature::from_fid(cdw10 as u8),
+            save: cdw10 & (1 << 31) != 0,
+            command_dwords: (layout & 0x7) as u8,
+            data: data.to_vec(),
+        })),
+    ))
 }
 
-fn parse_sanitize_complete_event(input: &[u8], revision: u8, length: u16) -> IResult<&[u8], Event> {
-    todo!()
+fn parse_sanitize_complete_event(
+    input: &[u8],
+    header: EventRecord<()>,
+    length: u16,
+) -> IResult<&[u8], Event> {
+    // TODO: decode the event data
+    let (input, data) = take(length)(input)?;
+    IResult::Ok((
+        input,
+        Event::SanitizeComplete(header.with_info(SanitizeCompleteInfo {
+            data: data.to_vec(),
+        })),
+    ))
 }
 
-fn parse_sanitize_start_event(input: &[u8], revision: u8, length: u16) -> IResult<&[u8], Event> {
-    todo!()
+fn parse_sanitize_start_event(
+    input: &[u8],
+    header: EventRecord<()>,
+    length: u16,
+) -> IResult<&[u8], Event> {
+    // TODO: decode the event data
+    let (input, data) = take(length)(input)?;
+    IResult::Ok((
+        input,
+        Event::SanitizeStart(header.with_info(SanitizeStartInfo {
+            data: data.to_vec(),
+        })),
+    ))
 }
 
 fn parse_format_nvm_complete_event(
     input: &[u8],
-    revision: u8,
+    header: EventRecord<()>,
     length: u16,
 ) -> IResult<&[u8], Event> {
-    todo!()
+    let (input, data) = take(length)(input)?;
+    // 03:00 - namespace id
+    // 11:04 - format progress and status, not tracked
+    let (_, nsid) = terminated(le_u32, take(8usize))(data)?;
+
+    IResult::Ok((
+        input,
+        Event::FormatNvmComplete(header.with_info(FormatNvmCompleteInfo { nsid })),
+    ))
 }
 
-fn parse_format_nvm_start_event(input: &[u8], revision: u8, length: u16) -> IResult<&[u8], Event> {
-    todo!()
+fn parse_format_nvm_start_event(
+    input: &[u8],
+    header: EventRecord<()>,
+    length: u16,
+) -> IResult<&[u8], Event> {
+    // TODO: decode the event data
+    let (input, data) = take(length)(input)?;
+    IResult::Ok((
+        input,
+        Event::FormatNvmStart(header.with_info(FormatNvmStartInfo {
+            data: data.to_vec(),
+        })),
+    ))
 }
 
-fn parse_change_namespace_event(input: &[u8], revision: u8, length: u16) -> IResult<&[u8], Event> {
-    todo!()
+fn parse_change_namespace_event(
+    input: &[u8],
+    header: EventRecord<()>,
+    length: u16,
+) -> IResult<&[u8], Event> {
+    // TODO: decode the event data
+    let (input, data) = take(length)(input)?;
+    IResult::Ok((
+        input,
+        Event::ChangeNamespace(header.with_info(ChangeNamespaceInfo {
+            data: data.to_vec(),
+        })),
+    ))
 }
 
-fn parse_nvm_hw_error_event(input: &[u8], revision: u8, length: u16) -> IResult<&[u8], Event> {
-    todo!()
+fn parse_nvm_hw_error_event(
+    input: &[u8],
+    header: EventRecord<()>,
+    length: u16,
+) -> IResult<&[u8], Event> {
+    // TODO: decode the event data
+    let (input, data) = take(length)(input)?;
+    IResult::Ok((
+        input,
+        Event::NvmHwError(header.with_info(NvmHwErrorInfo {
+            data: data.to_vec(),
+        })),
+    ))
 }
 
-fn parse_por_event(input: &[u8], revision: u8, length: u16) -> IResult<&[u8], Event> {
-    todo!()
+fn parse_por_event(input: &[u8], header: EventRecord<()>, length: u16) -> IResult<&[u8], Event> {
+    let (input, data) = take(length)(input)?;
+    // 07:00 - firmware revision
+    let (data, firmware_revision) = take(8usize)(data)?;
+    // the rest is a list of controller reset information descriptors
+    let num_resets = data.len() / CONTROLLER_RESET_INFO_LEN;
+    let (_, resets) = count(parse_controller_reset_info, num_resets)(data)?;
+
+    IResult::Ok((
+        input,
+        Event::Por(header.with_info(PorInfo {
+            firmware_revision: clean_str(firmware_revision),
+            resets,
+        })),
+    ))
 }
 
-fn parse_timestamp_change_event(input: &[u8], revision: u8, length: u16) -> IResult<&[u8], Event> {
-    todo!()
+const CONTROLLER_RESET_INFO_LEN: usize = 36;
+
+fn parse_controller_reset_info(input: &[u8]) -> IResult<&[u8], ControllerResetInfo> {
+    // 01:00 - controller id
+    let (input, ctrl_id) = le_u16(input)?;
+    // 02 - firmware activation
+    let (input, firmware_activation) = le_u8(input)?;
+    // 03 - operation in progress
+    // 15:04 - reserved
+    let (input, operation_in_progress) = terminated(le_u8, take(12usize))(input)?;
+    // 19:16 - controller power cycle
+    let (input, power_cycle_count) = le_u32(input)?;
+    // 27:20 - power on milliseconds
+    let (input, power_on_ms) = le_u64(input)?;
+    // 35:28 - controller timestamp
+    let (input, timestamp) = parse_timestamp(input)?;
+
+    IResult::Ok((
+        input,
+        ControllerResetInfo {
+            ctrl_id,
+            firmware_activation: firmware_activation & 0x1 != 0,
+            operation_in_progress,
+            power_cycle_count,
+            power_on_ms,
+            timestamp,
+        },
+    ))
+}
+
+fn parse_timestamp_change_event(
+    input: &[u8],
+    header: EventRecord<()>,
+    length: u16,
+) -> IResult<&[u8], Event> {
+    // TODO: decode the event data
+    let (input, data) = take(length)(input)?;
+    IResult::Ok((
+        input,
+        Event::TimestampChange(header.with_info(TimestampChangeInfo {
+            data: data.to_vec(),
+        })),
+    ))
 }
 
-fn parse_fw_commit_event(input: &[u8], revision: u8, length: u16) -> IResult<&[u8], Event> {
-    todo!()
+fn parse_fw_commit_event(
+    input: &[u8],
+    header: EventRecord<()>,
+    length: u16,
+) -> IResult<&[u8], Event> {
+    let (input, data) = take(length)(input)?;
+    // 07:00 - old firmware revision
+    let (data, previous_revision) = take(8usize)(data)?;
+    // 15:08 - new firmware revision
+    let (data, new_revision) = take(8usize)(data)?;
+    // 16 - firmware commit action
+    let (data, commit_action) = le_u8(data)?;
+    // 17 - firmware slot
+    let (data, slot_number) = le_u8(data)?;
+    // 18 - status code type for the firmware commit command
+    let (data, status_code_type) = le_u8(data)?;
+    // 19 - status returned for the firmware commit command
+    let (data, commit_status) = le_u8(data)?;
+    // 21:20 - vendor assigned firmware commit result
+    // 23:22 - reserved
+    let (_, commit_result) = terminated(le_u16, take(2usize))(data)?;
+
+    IResult::Ok((
+        input,
+        Event::FwCommit(header.with_info(FwCommitInfo {
+            previous_revision: clean_str(previous_revision),
+            new_revision: clean_str(new_revision),
+            commit_action,
+            slot_number,
+            status_code_type,
+            commit_status,
+            commit_result: FwCommitResult(commit_result),
+        })),
+    ))
 }
 
-fn parse_smart_event(input: &[u8], revision: u8, length: u16) -> IResult<&[u8], Event> {
-    todo!()
+fn parse_smart_event(input: &[u8], header: EventRecord<()>, length: u16) -> IResult<&[u8], Event> {
+    let (input, data) = take(length)(input)?;
+    // 00 - critical warning
+    let (data, critical_warning) = le_u8(data)?;
+    // 02:01 - composite temperature
+    let (data, composite_temperature) = le_u16(data)?;
+    // 03 - available spare
+    let (data, available_spare) = le_u8(data)?;
+    // 04 - available spare threshold
+    let (data, available_spare_threshold) = le_u8(data)?;
+    // 05 - percentage used
+    let (data, percentage_used) = le_u8(data)?;
+    // 06 - endurance group critical warning summary
+    // 31:07 - reserved
+    let (data, endurance_group_critical_warning) = terminated(le_u8, take(25usize))(data)?;
+    // 47:32 - data units read
+    let (data, data_units_read) = le_u128(data)?;
+    // 63:48 - data units written
+    let (data, data_units_written) = le_u128(data)?;
+    // 79:64 - host read commands
+    let (data, host_read_commands) = le_u128(data)?;
+    // 95:80 - host write commands
+    let (data, host_write_commands) = le_u128(data)?;
+    // 111:96 - controller busy time
+    let (data, io_controller_busy_time_minutes) = le_u128(data)?;
+    // 127:112 - power cycles
+    let (data, power_cycles) = le_u128(data)?;
+    // 143:128 - power on hours
+    let (data, power_on_hours) = le_u128(data)?;
+    // 159:144 - unsafe shutdowns
+    let (data, unsafe_shutdowns) = le_u128(data)?;
+    // 175:160 - media and data integrity errors
+    let (data, media_errors) = le_u128(data)?;
+    // 191:176 - number of error information log entries
+    let (data, num_err_log_entries) = le_u128(data)?;
+    // 195:192 - warning composite temperature time
+    let (data, warning_temp_time) = le_u32(data)?;
+    // 199:196 - critical composite temperature time
+    let (data, critical_comp_time) = le_u32(data)?;
+    // 215:200 - temperature sensors 1-8
+    let mut temp_sensors = [0u16; 8];
+    let (data, _) = fill(le_u16, &mut temp_sensors)(data)?;
+    // 219:216 - thermal management temperature 1 transition count
+    let (data, thermal_management_temp1_transition_count) = le_u32(data)?;
+    // 223:220 - thermal management temperature 2 transition count
+    // 511:224 - not tracked
+    let (_, thermal_management_temp2_transition_count) = terminated(le_u32, take(288usize))(data)?;
+
+    IResult::Ok((
+        input,
+        Event::SmartHealth(header.with_info(SmartHealthInfo {
+            critical_warning: CriticalWarning(critical_warning),
+            composite_temperature,
+            available_spare,
+            available_spare_threshold,
+            percentage_used,
+            endurance_group_critical_warning,
+            data_units_read,
+            data_units_written,
+            host_read_commands,
+            host_write_commands,
+            io_controller_busy_time_minutes,
+            power_cycles,
+            power_on_hours,
+            unsafe_shutdowns,
+            media_errors,
+            num_err_log_entries,
+            warning_temp_time,
+            critical_comp_time,
+            temp_sensors,
+            thermal_management_temp1_transition_count,
+            thermal_management_temp2_transition_count,
+            warning_temperature_threshold_kelvin: None,
+            critical_temperature_threshold_kelvin: None,
+        })),
+    ))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::pel::test_helpers::*;
+
+    fn make_header() -> EventRecord<()> {
+        let bytes = make_event_bytes(0, 1, 1, 0, &[]);
+        let (_, (_, header)) = parse_event_header(&bytes).unwrap();
+        header
+    }
 
     #[test]
     fn test_log_header() {
-        todo!()
+        let mut header = [0u8; 512];
+        header[0] = 0x0d;
+        header[4..8].copy_from_slice(&3u32.to_le_bytes());
+        header[8..16].copy_from_slice(&1024u64.to_le_bytes());
+        header[16] = 1;
+        header[18..20].copy_from_slice(&512u16.to_le_bytes());
+        header[20..26].copy_from_slice(&[0x0, 0x11, 0x22, 0x33, 0x44, 0x55]);
+        header[26] = 0b00000011;
+        header[28..44].copy_from_slice(&1234u128.to_le_bytes());
+        header[44..52].copy_from_slice(&56u64.to_le_bytes());
+        header[52..54].copy_from_slice(&0x144du16.to_le_bytes());
+        header[54..56].copy_from_slice(&0x1028u16.to_le_bytes());
+        header[56..63].copy_from_slice(b"SN12345");
+        header[76..84].copy_from_slice(b"MODEL-01");
+        header[116..132].copy_from_slice(b"nqn.2014-08.test");
+        header[480] = 0b00000010;
+
+        let (remainder, pel) = parse_log_header(&header).unwrap();
+        assert_eq!(remainder.len(), 0);
+        assert_eq!(pel.num_events, 3);
+        assert_eq!(pel.len, 1024);
+        assert_eq!(pel.revision, 1);
+        assert_eq!(pel.header_len, 512);
+        assert!(pel.timestamp.equivalent_to(&Timestamp {
+            ms: Duration::from_millis(0x554433221100),
+            synch: TimestampSynch::Skipped,
+            origin: TimestampOrigin::SetFeature,
+        }));
+        assert_eq!(pel.power_on_hours, 1234);
+        assert_eq!(pel.power_cycle_count, 56);
+        assert_eq!(pel.vid, 0x144d);
+        assert_eq!(pel.ssvid, 0x1028);
+        assert_eq!(pel.serial_num, "SN12345");
+        assert_eq!(pel.model_num, "MODEL-01");
+        assert_eq!(pel.subnqn, "nqn.2014-08.test");
+        assert_eq!(pel.supp_events.0[0], 0b00000010);
+        assert!(pel.events.is_none());
+
+        assert!(parse_log_header(&header[..511]).is_err());
+    }
+
+    #[test]
+    fn test_log_header_rev_2_fields() {
+        let mut header = make_minimal_log_header();
+        header[372..374].copy_from_slice(&7u16.to_le_bytes());
+        header[374..378].copy_from_slice(&(1u32 << 18 | 0b01 << 16 | 0x1234).to_le_bytes());
+
+        // ignored in revision 1 logs
+        let (_, pel) = parse_log_header(&header).unwrap();
+        assert_eq!(pel.generation, None);
+        assert_eq!(pel.reporting_context, None);
+
+        header[16] = 2;
+        let (remainder, pel) = parse_log_header(&header).unwrap();
+        assert_eq!(remainder.len(), 0);
+        assert_eq!(pel.generation, Some(7));
+        assert_eq!(
+            pel.reporting_context,
+            Some(ReportingContext::NVMPort(0x1234))
+        );
+
+        header[374..378].copy_from_slice(&(1u32 << 18 | 0b10 << 16 | 0x2).to_le_bytes());
+        let (_, pel) = parse_log_header(&header).unwrap();
+        assert_eq!(pel.reporting_context, Some(ReportingContext::MiPort(0x2)));
+
+        // port fields don't matter without the exists bit
+        header[374..378].copy_from_slice(&(0b10u32 << 16 | 0x2).to_le_bytes());
+        let (_, pel) = parse_log_header(&header).unwrap();
+        assert_eq!(pel.reporting_context, Some(ReportingContext::DoesNotExist));
     }
 
     #[test]
     fn test_log_event_header() {
-        todo!()
+        let mut header = [0u8; 26];
+        header[0] = SMART_HEALTH;
+        header[1] = 1;
+        header[2] = 21;
+        header[4..6].copy_from_slice(&7u16.to_le_bytes());
+        header[6..12].copy_from_slice(&1000u64.to_le_bytes()[..6]);
+        header[20..22].copy_from_slice(&4u16.to_le_bytes());
+        header[22..24].copy_from_slice(&516u16.to_le_bytes());
+
+        let (remainder, (event_type, record)) = parse_event_header(&header).unwrap();
+        assert_eq!(remainder.len(), 2);
+        assert_eq!(event_type, SMART_HEALTH);
+        assert_eq!(record.revision, 1);
+        assert_eq!(record.header_len, 24);
+        assert_eq!(record.ctrl_id, 7);
+        assert!(record.timestamp.equivalent_to(&Timestamp {
+            ms: Duration::from_millis(1000),
+            ..Default::default()
+        }));
+        assert_eq!(record.vendor_info_len, 4);
+        assert_eq!(record.len, 540);
+
+        // a longer header skips the extra bytes
+        header[2] = 23;
+        let (remainder, (_, record)) = parse_event_header(&header).unwrap();
+        assert_eq!(remainder.len(), 0);
+        assert_eq!(record.header_len, 26);
+
+        assert!(parse_event_header(&header[..23]).is_err());
+    }
+
+    #[test]
+    fn test_parse_smart_event() {
+        let mut payload = [0u8; SMART_EVENT_LEN];
+        payload[0] = 0b0000_0101;
+        payload[1..3].copy_from_slice(&310u16.to_le_bytes());
+        payload[3] = 95;
+        payload[4] = 10;
+        payload[5] = 7;
+        payload[6] = 1;
+        for (i, offset) in (32..192).step_by(16).enumerate() {
+            payload[offset..offset + 16].copy_from_slice(&(i as u128 + 1).to_le_bytes());
+        }
+        payload[192..196].copy_from_slice(&11u32.to_le_bytes());
+        payload[196..200].copy_from_slice(&12u32.to_le_bytes());
+        for i in 0..8 {
+            let offset = 200 + i * 2;
+            payload[offset..offset + 2].copy_from_slice(&(300 + i as u16).to_le_bytes());
+        }
+        payload[216..220].copy_from_slice(&13u32.to_le_bytes());
+        payload[220..224].copy_from_slice(&14u32.to_le_bytes());
+        payload[511] = 0xff;
+
+        let mut bytes = make_event_bytes(SMART_HEALTH, 1, 3, 1000, &payload);
+        bytes.push(0xaa);
+        let (input, (_, header)) = parse_event_header(&bytes).unwrap();
+        let (remainder, event) = parse_smart_event(input, header, SMART_EVENT_LEN as u16).unwrap();
+        assert_eq!(remainder, &[0xaa]);
+
+        let Event::SmartHealth(event) = event else {
+            panic!("expected a SMART / health event, got {event:?}");
+        };
+        assert_eq!(event.ctrl_id, 3);
+        assert_eq!(event.timestamp.millis(), 1000);
+        let info = event.info;
+        assert_eq!(info.critical_warning, CriticalWarning(0b0000_0101));
+        assert!(info.critical_warning.available_spare_below_threshold());
+        assert!(info.critical_warning.reliability_degraded());
+        assert_eq!(info.composite_temperature, 310);
+        assert_eq!(info.available_spare, 95);
+        assert_eq!(info.available_spare_threshold, 10);
+        assert_eq!(info.percentage_used, 7);
+        assert_eq!(info.endurance_group_critical_warning, 1);
+        assert_eq!(info.data_units_read, 1);
+        assert_eq!(info.data_units_written, 2);
+        assert_eq!(info.host_read_commands, 3);
+        assert_eq!(info.host_write_commands, 4);
+        assert_eq!(info.io_controller_busy_time_minutes, 5);
+        assert_eq!(info.power_cycles, 6);
+        assert_eq!(info.power_on_hours, 7);
+        assert_eq!(info.unsafe_shutdowns, 8);
+        assert_eq!(info.media_errors, 9);
+        assert_eq!(info.num_err_log_entries, 10);
+        assert_eq!(info.warning_temp_time, 11);
+        assert_eq!(info.critical_comp_time, 12);
+        assert_eq!(info.temp_sensors, [300, 301, 302, 303, 304, 305, 306, 307]);
+        assert_eq!(info.thermal_management_temp1_transition_count, 13);
+        assert_eq!(info.thermal_management_temp2_transition_count, 14);
+        assert_eq!(info.warning_temperature_threshold_kelvin, None);
+
+        // the body must be long enough for every field
+        let (input, (_, header)) = parse_event_header(&bytes).unwrap();
+        assert!(parse_smart_event(&input[..200], header, 200).is_err());
+    }
+
+    #[test]
+    fn test_parse_fw_commit_event() {
+        let mut bytes = make_fw_commit_event_bytes(1, 2, 1000, "1.0", "FW2.0");
+        let body = &mut bytes[EVENT_HEADER_LEN..];
+        body[16] = 3;
+        body[17] = 2;
+        body[18] = 1;
+        body[19] = 0x0b;
+        body[20..22].copy_from_slice(&0x1234u16.to_le_bytes());
+        bytes.push(0xaa);
+
+        let (input, (_, header)) = parse_event_header(&bytes).unwrap();
+        let (remainder, event) =
+            parse_fw_commit_event(input, header, FW_COMMIT_EVENT_LEN as u16).unwrap();
+        assert_eq!(remainder, &[0xaa]);
+
+        let Event::FwCommit(event) = event else {
+            panic!("expected a firmware commit event, got {event:?}");
+        };
+        assert_eq!(event.ctrl_id, 2);
+        assert_eq!(event.info.previous_revision, "1.0");
+        assert_eq!(event.info.new_revision, "FW2.0");
+        assert_eq!(event.info.commit_action, 3);
+        assert_eq!(event.info.slot_number, 2);
+        assert_eq!(event.info.status_code_type, 1);
+        assert_eq!(event.info.commit_status, 0x0b);
+        assert!(!event.info.command_succeeded());
+        assert_eq!(event.info.commit_result, FwCommitResult(0x1234));
+
+        assert!(parse_fw_commit_event(input, make_header(), 16).is_err());
+    }
+
+    #[test]
+    fn test_parse_por_event() {
+        let mut body = vec![0u8; 8 + 2 * 36];
+        body[..4].copy_from_slice(b"1.2A");
+        for (i, reset) in body[8..].chunks_mut(36).enumerate() {
+            reset[0..2].copy_from_slice(&(i as u16 + 1).to_le_bytes());
+            reset[2] = i as u8;
+            reset[3] = 4;
+            reset[16..20].copy_from_slice(&(10 + i as u32).to_le_bytes());
+            reset[20..28].copy_from_slice(&(5000 + i as u64).to_le_bytes());
+            reset[28..34].copy_from_slice(&(7000 + i as u64).to_le_bytes()[..6]);
+        }
+        let bytes = make_event_bytes(POR, 1, 1, 1000, &body);
+
+        let (input, (_, header)) = parse_event_header(&bytes).unwrap();
+        let (remainder, event) = parse_por_event(input, header, body.len() as u16).unwrap();
+        assert_eq!(remainder.len(), 0);
+
+        let Event::Por(event) = event else {
+            panic!("expected a power on or reset event, got {event:?}");
+        };
+        assert_eq!(event.info.firmware_revision, "1.2A");
+        assert_eq!(event.info.resets.len(), 2);
+        for (i, reset) in event.info.resets.iter().enumerate() {
+            assert_eq!(reset.ctrl_id, i as u16 + 1);
+            assert_eq!(reset.firmware_activation, i == 1);
+            assert_eq!(reset.operation_in_progress, 4);
+            assert_eq!(reset.power_cycle_count, 10 + i as u32);
+            assert_eq!(reset.power_on_ms, 5000 + i as u64);
+            assert_eq!(reset.timestamp.millis(), 7000 + i as u64);
+        }
+
+        // no descriptors
+        let (_, event) = parse_por_event(&body, make_header(), 8).unwrap();
+        let Event::Por(event) = event else {
+            panic!("expected a power on or reset event, got {event:?}");
+        };
+        assert!(event.info.resets.is_empty());
+    }
+
+    #[test]
+    fn test_parse_thermal_excursion_event() {
+        let (remainder, event) =
+            parse_thermal_excursion_event(&[0x01, 80, 0xaa], make_header(), 2).unwrap();
+        assert_eq!(remainder, &[0xaa]);
+        let Event::ThermalExcursion(event) = event else {
+            panic!("expected a thermal excursion event, got {event:?}");
+        };
+        assert!(event.info.over_threshold);
+        assert!(!event.info.under_threshold);
+        assert_eq!(event.info.threshold_kelvin, 353);
+        assert_eq!(event.info.threshold_celsius(), 80.0);
+        assert!(event.info.is_critical());
+
+        let (_, event) = parse_thermal_excursion_event(&[0x00, 0], make_header(), 2).unwrap();
+        let Event::ThermalExcursion(event) = event else {
+            panic!("expected a thermal excursion event, got {event:?}");
+        };
+        assert!(event.info.under_threshold);
+        assert_eq!(event.info.threshold_kelvin, 273);
+
+        assert!(parse_thermal_excursion_event(&[0x01], make_header(), 1).is_err());
+    }
+
+    #[test]
+    fn test_parse_format_nvm_complete_event() {
+        let mut body = [0u8; 12];
+        body[0..4].copy_from_slice(&7u32.to_le_bytes());
+        // format progress and status
+        body[4] = 100;
+        body[5] = 1;
+        let bytes = make_event_bytes(FORMAT_NVM_COMPLETE, 1, 1, 1000, &body);
+
+        let (remainder, event) = parse_event(&bytes, 0x144d, false).unwrap();
+        assert_eq!(remainder.len(), 0);
+        let Event::FormatNvmComplete(event) = event else {
+            panic!("expected a format NVM complete event, got {event:?}");
+        };
+        assert_eq!(event.info.nsid, 7);
+
+        assert!(parse_format_nvm_complete_event(&body, make_header(), 2).is_err());
+    }
+
+    #[test]
+    fn test_parse_set_feature_event() {
+        let mut body = vec![0u8; 12];
+        // one command dword logged
+        body[0..4].copy_from_slice(&2u32.to_le_bytes());
+        body[4..8].copy_from_slice(&(1u32 << 31 | 0x06).to_le_bytes());
+        body[8..12].copy_from_slice(&1u32.to_le_bytes());
+        let bytes = make_event_bytes(SET_FEATURE, 1, 1, 1000, &body);
+
+        let (remainder, event) = parse_event(&bytes, 0x144d, false).unwrap();
+        assert_eq!(remainder.len(), 0);
+        let Event::SetFeature(event) = event else {
+            panic!("expected a set feature event, got {event:?}");
+        };
+        assert_eq!(event.info.feature, Feature::VolatileWriteCache);
+        assert!(event.info.save);
+        assert_eq!(event.info.command_dwords, 2);
+        assert_eq!(event.info.data, vec![1, 0, 0, 0]);
+
+        body[4..8].copy_from_slice(&0x55u32.to_le_bytes());
+        let (_, event) = parse_set_feature_event(&body, make_header(), 12).unwrap();
+        let Event::SetFeature(event) = event else {
+            panic!("expected a set feature event, got {event:?}");
+        };
+        assert_eq!(event.info.feature, Feature::Unknown { fid: 0x55 });
+        assert_eq!(event.info.data, vec![1, 0, 0, 0]);
+        assert!(!event.info.save);
+
+        assert!(parse_set_feature_event(&body, make_header(), 6).is_err());
+    }
+
+    #[test]
+    fn test_parse_vendor_specific_event() {
+        let bytes = make_event_bytes(VENDOR_SPECIFC, 1, 1, 1000, &[1, 2, 3, 4, 5]);
+
+        let (remainder, event) = parse_event(&bytes, 0x144d, false).unwrap();
+        assert_eq!(remainder.len(), 0);
+        let Event::VendorSpecifc(event) = event else {
+            panic!("expected a vendor specific event, got {event:?}");
+        };
+        assert_eq!(event.info.vendor_id, 0x144d);
+        assert_eq!(event.info.data, [1, 2, 3, 4, 5]);
+    }
+}
+
+// The in-memory structs don't mirror the wire format, so these check how many bytes each parser
+// consumes against the sizes in the spec instead of using `size_of`
+#[cfg(test)]
+mod size_checks {
+    use super::*;
+    use crate::pel::test_helpers::*;
+
+    #[test]
+    fn test_timestamp_size() {
+        let (remainder, _) = parse_timestamp(&[0u8; 9]).unwrap();
+        assert_eq!(remainder.len(), 1);
+        assert!(parse_timestamp(&[0u8; 7]).is_err());
+    }
+
+    #[test]
+    fn test_log_header_size() {
+        let (remainder, _) = parse_log_header(&[0u8; 513]).unwrap();
+        assert_eq!(remainder.len(), 1);
+        assert!(parse_log_header(&[0u8; 511]).is_err());
+    }
+
+    #[test]
+    fn test_event_header_size() {
+        let mut header = [0u8; 25];
+        header[2] = 21;
+        let (remainder, (_, record)) = parse_event_header(&header).unwrap();
+        assert_eq!(remainder.len(), 1);
+        assert_eq!(record.header_len, 24);
+        assert!(parse_event_header(&header[..23]).is_err());
+    }
+
+    fn make_header() -> EventRecord<()> {
+        let bytes = make_event_bytes(0, 1, 1, 0, &[]);
+        let (_, (_, header)) = parse_event_header(&bytes).unwrap();
+        header
+    }
+
+    type BodyParser = fn(&[u8], EventRecord<()>, u16) -> IResult<&[u8], Event>;
+
+    // Parses a zeroed body of `size` bytes, plus one trailing byte the parser must leave alone
+    fn assert_body_size(parse: BodyParser, size: u16) {
+        let body = vec![0u8; size as usize + 1];
+        let (remainder, _) = parse(&body, make_header(), size).unwrap();
+        assert_eq!(remainder.len(), 1);
+        assert!(parse(&body[..size as usize - 1], make_header(), size - 1).is_err());
+    }
+
+    #[test]
+    fn test_smart_event_size() {
+        assert_body_size(parse_smart_event, 512);
+    }
+
+    #[test]
+    fn test_fw_commit_event_size() {
+        assert_body_size(parse_fw_commit_event, 24);
+    }
+
+    #[test]
+    fn test_thermal_excursion_event_size() {
+        assert_body_size(parse_thermal_excursion_event, 2);
+    }
+
+    #[test]
+    fn test_format_nvm_complete_event_size() {
+        assert_body_size(parse_format_nvm_complete_event, 12);
+    }
+
+    #[test]
+    fn test_set_feature_event_size() {
+        // the layout and CDW10 are always logged
+        assert_body_size(parse_set_feature_event, 8);
+    }
+
+    #[test]
+    fn test_por_event_size() {
+        // the firmware revision, then one controller reset information descriptor
+        assert_body_size(parse_por_event, 8);
+        let (_, event) = parse_por_event(&[0u8; 45], make_header(), 44).unwrap();
+        let Event::Por(event) = event else {
+            panic!("expected a power on or reset event, got {event:?}");
+        };
+        assert_eq!(event.info.resets.len(), 1);
     }
 }
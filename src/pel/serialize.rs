@@ -0,0 +1,876 @@
+// Writes parsed logs back out in the little endian on-disk format
+
+use super::*;
+
+pub trait ToBytesLE {
+    fn write_le(&self, buf: &mut Vec<u8>);
+
+    fn to_bytes_le(&self) -> Vec<u8> {
+        let mut v = Vec::new();
+        self.write_le(&mut v);
+        v
+    }
+}
+
+/// Event data along with the event type it's logged under.
+pub trait EventInfo: ToBytesLE {
+    const EVENT_TYPE: u8;
+
+    fn event_type(&self) -> u8 {
+        Self::EVENT_TYPE
+    }
+}
+
+// Writes `s` into a fixed size field, truncating or padding it with `pad`
+fn write_str(buf: &mut Vec<u8>, s: &str, len: usize, pad: u8) {
+    let bytes = &s.as_bytes()[..s.len().min(len)];
+    buf.extend_from_slice(bytes);
+    buf.resize(buf.len() + len - bytes.len(), pad);
+}
+
+impl ToBytesLE for Timestamp {
+    fn write_le(&self, buf: &mut Vec<u8>) {
+        // 05:00 - timestamp milliseconds
+        buf.extend_from_slice(&self.millis().to_le_bytes()[..6]);
+        let origin = match self.origin {
+            TimestampOrigin::Reset => 0,
+            TimestampOrigin::SetFeature => 1,
+            TimestampOrigin::Unknown(origin) => origin,
+        };
+        let synch = match self.synch {
+            TimestampSynch::Continuous => 0,
+            TimestampSynch::Skipped => 1,
+            TimestampSynch::Unknown(synch) => synch,
+        };
+        // 06 - attributes, bits 03:01 origin and bit 00 synch
+        buf.push((origin & 0x7) << 1 | synch & 0x1);
+        // 07 - reserved
+        buf.push(0);
+    }
+}
+
+impl ToBytesLE for Pel {
+    fn write_le(&self, buf: &mut Vec<u8>) {
+        let event_data = self.serialize_events_only();
+        let start = buf.len();
+        buf.extend_from_slice(&self.header_bytes(event_data.len()));
+        // anything past the fields we know about
+        buf.resize(
+            start + (self.header_len as usize).max(MINIMUM_LOG_SIZE as usize),
+            0,
+        );
+        buf.extend_from_slice(&event_data);
+    }
+}
+
+impl Pel {
+    /// The 512 byte log header as [`ToBytesLE::to_bytes_le`] writes it. Followed by
+    /// [`Pel::serialize_events_only`] that's the whole log, unless `header_len` is longer than
+    /// 512 bytes.
+    pub fn serialize_header_only(&self) -> [u8; MINIMUM_LOG_SIZE as usize] {
+        self.header_bytes(self.serialize_events_only().len())
+    }
+
+    /// The event records without the log header.
+    pub fn serialize_events_only(&self) -> Vec<u8> {
+        let mut buf = Vec::new();
+        for event in self.events.iter().flatten() {
+            event.write_le(&mut buf);
+        }
+        buf
+    }
+
+    fn header_bytes(&self, event_data_len: usize) -> [u8; MINIMUM_LOG_SIZE as usize] {
+        let num_events = self.events.as_ref().map_or(0, Vec::len) as u32;
+        let header_len = (self.header_len as usize).max(MINIMUM_LOG_SIZE as usize);
+        let len = (header_len + event_data_len) as u64;
+
+        let mut buf = Vec::with_capacity(MINIMUM_LOG_SIZE as usize);
+        self.write_header(&mut buf, self.header_len, num_events, len);
+        buf.try_into().unwrap()
+    }
+
+    // Writes the 512 bytes of header fields we know about. TNEV and TTL are passed in so they
+    // match the events that are actually written
+    fn write_header(&self, buf: &mut Vec<u8>, header_len: u16, num_events: u32, len: u64) {
+        // 00 - log id
+        // 03:01 - reserved
+        buf.extend_from_slice(&[PEL_LOG_ID, 0, 0, 0]);
+        // 07:04 - total number of events
+        buf.extend_from_slice(&num_events.to_le_bytes());
+        // 15:08 - total log length
+        buf.extend_from_slice(&len.to_le_bytes());
+        // 16 - log revision
+        // 17 - reserved
+        buf.extend_from_slice(&[self.revision, 0]);
+        // 19:18 - log header length
+        buf.extend_from_slice(&header_len.to_le_bytes());
+        // 27:20 - timestamp
+        self.timestamp.write_le(buf);
+        // 43:28 - power on hours
+        buf.extend_from_slice(&self.power_on_hours.to_le_bytes());
+        // 51:44 - power cycle count
+        buf.extend_from_slice(&self.power_cycle_count.to_le_bytes());
+        // 53:52 - pci vendor id
+        buf.extend_from_slice(&self.vid.to_le_bytes());
+        // 55:54 - pci subsystem vendor id
+        buf.extend_from_slice(&self.ssvid.to_le_bytes());
+        // 75:56 - serial number
+        write_str(buf, &self.serial_num, 20, b' ');
+        // 115:76 - model number
+        write_str(buf, &self.model_num, 40, b' ');
+        // 371:116 - nvm subsystem nvme qualified name
+        write_str(buf, &self.subnqn, 256, 0);
+        // 373:372 - generation number
+        buf.extend_from_slice(&self.generation.unwrap_or_default().to_le_bytes());
+        // 377:374 - reporting context information
+        let reporting_context = match self.reporting_context {
+            None | Some(ReportingContext::DoesNotExist) => 0,
+            Some(ReportingContext::NVMPort(port)) => 1 << 18 | 0b01 << 16 | port as u32,
+            Some(ReportingContext::MiPort(port)) => 1 << 18 | 0b10 << 16 | port as u32,
+        };
+        buf.extend_from_slice(&reporting_context.to_le_bytes());
+        // 479:378 - reserved
+        buf.resize(buf.len() + 102, 0);
+        // 511:480 - supported events bitmap
+        buf.extend_from_slice(&self.supp_events.0);
+    }
+
+    /// CRC32 over the event records as they'd be written out, to check two logs hold the same
+    /// events or that a parsed log wasn't modified since.
+    pub fn compute_log_checksum(&self) -> u32 {
+        crc32fast::hash(&self.serialize_events_only())
+    }
+
+    pub fn checksum_matches(&self, expected: u32) -> bool {
+        self.compute_log_checksum() == expected
+    }
+
+    /// Like [`ToBytesLE::to_bytes_le`], but fails instead of truncating fields that don't fit
+    /// the spec layout. The log header is always 512 bytes.
+    pub fn serialize_to_nvme_wire_format(&self) -> Result<Vec<u8>, PelError> {
+        for (field, value, max) in [
+            ("serial number", &self.serial_num, 20),
+            ("model number", &self.model_num, 40),
+            ("SUBNQN", &self.subnqn, 256),
+        ] {
+            if value.len() > max {
+                return Err(PelError::FieldTooLong {
+                    field,
+                    len: value.len(),
+                    max,
+                });
+            }
+        }
+
+        let events = self.events.as_deref().unwrap_or_default();
+        let mut event_data = Vec::new();
+        for event in events {
+            let start = event_data.len();
+            event.write_le(&mut event_data);
+            // 02 - EHL, the event length (EL) after it only has 16 bits
+            let header_len = event_data[start + 2] as usize + 3;
+            let len = event_data.len() - start - header_len;
+            if len > u16::MAX as usize {
+                return Err(PelError::FieldTooLong {
+                    field: "event length",
+                    len,
+                    max: u16::MAX as usize,
+                });
+            }
+        }
+
+        let mut buf = Vec::with_capacity(MINIMUM_LOG_SIZE as usize + event_data.len());
+        let len = MINIMUM_LOG_SIZE + event_data.len() as u64;
+        self.write_header(&mut buf, MINIMUM_LOG_SIZE as u16, events.len() as u32, len);
+        buf.extend_from_slice(&event_data);
+        Ok(buf)
+    }
+
+    /// Parses a log from hex, such as `xxd -p` output. Whitespace is ignored.
+    pub fn from_hex_string(hex: &str) -> Result<Pel, PelError> {
+        let hex: String = hex.split_whitespace().collect();
+        let bytes = hex::decode(hex).map_err(|_| PelError::InvalidHexInput)?;
+        parse_pel(&bytes)
+    }
+
+    pub fn to_hex_string(&self) -> String {
+        hex::encode(self.to_bytes_le())
+    }
+}
+
+impl<T: EventInfo> ToBytesLE for EventRecord<T> {
+    fn write_le(&self, buf: &mut Vec<u8>) {
+        let body = self.info.to_bytes_le();
+        let vendor_info = self
+            .vendor_info
+            .as_ref()
+            .map(|info| info.raw.as_slice())
+            .unwrap_or_default();
+        let header_len = self.header_len.max(EVENT_HEADER_LEN as u8);
+
+        let start = buf.len();
+        // 00 - event type
+        // 01 - event type revision
+        // 02 - event header length
+        // 03 - reserved
+        buf.extend_from_slice(&[self.info.event_type(), self.revision, header_len - 3, 0]);
+        // 05:04 - controller id
+        buf.extend_from_slice(&self.ctrl_id.to_le_bytes());
+        // 13:06 - event timestamp
+        self.timestamp.write_le(buf);
+        // 19:14 - reserved
+        buf.resize(buf.len() + 6, 0);
+        // 21:20 - vendor specific information length
+        buf.extend_from_slice(&(vendor_info.len() as u16).to_le_bytes());
+        // 23:22 - event length
+        buf.extend_from_slice(&((vendor_info.len() + body.len()) as u16).to_le_bytes());
+        buf.resize(start + header_len as usize, 0);
+
+        buf.extend_from_slice(vendor_info);
+        buf.extend_from_slice(&body);
+    }
+}
+
+impl ToBytesLE for Event {
+    fn write_le(&self, buf: &mut Vec<u8>) {
+        match self {
+            Event::SmartHealth(record) => record.write_le(buf),
+            Event::FwCommit(record) => record.write_le(buf),
+            Event::TimestampChange(record) => record.write_le(buf),
+            Event::Por(record) => record.write_le(buf),
+            Event::NvmHwError(record) => record.write_le(buf),
+            Event::ChangeNamespace(record) => record.write_le(buf),
+            Event::FormatNvmStart(record) => record.write_le(buf),
+            Event::FormatNvmComplete(record) => record.write_le(buf),
+            Event::SanitizeStart(record) => record.write_le(buf),
+            Event::SanitizeComplete(record) => record.write_le(buf),
+            Event::SetFeature(record) => record.write_le(buf),
+            Event::TelementryLogCreated(record) => record.write_le(buf),
+            Event::ThermalExcursion(record) => record.write_le(buf),
+            Event::VendorSpecifc(record) => record.write_le(buf),
+            Event::TcgDefined(record) => record.write_le(buf),
+            Event::Unknown(record) => record.write_le(buf),
+        }
+    }
+}
+
+impl ToBytesLE for SmartHealthInfo {
+    fn write_le(&self, buf: &mut Vec<u8>) {
+        let start = buf.len();
+        // 00 - critical warning
+        buf.push(self.critical_warning.0);
+        // 02:01 - composite temperature
+        buf.extend_from_slice(&self.composite_temperature.to_le_bytes());
+        // 03 - available spare
+        // 04 - available spare threshold
+        // 05 - percentage used
+        // 06 - endurance group critical warning summary
+        buf.extend_from_slice(&[
+            self.available_spare,
+            self.available_spare_threshold,
+            self.percentage_used,
+            self.endurance_group_critical_warning,
+        ]);
+        // 31:07 - reserved
+        buf.resize(start + 32, 0);
+        // 191:32 - data units read through number of error information log entries
+        for counter in [
+            self.data_units_read,
+            self.data_units_written,
+            self.host_read_commands,
+            self.host_write_commands,
+            self.io_controller_busy_time_minutes,
+            self.power_cycles,
+            self.power_on_hours,
+            self.unsafe_shutdowns,
+            self.media_errors,
+            self.num_err_log_entries,
+        ] {
+            buf.extend_from_slice(&counter.to_le_bytes());
+        }
+        // 195:192 - warning composite temperature time
+        buf.extend_from_slice(&self.warning_temp_time.to_le_bytes());
+        // 199:196 - critical composite temperature time
+        buf.extend_from_slice(&self.critical_comp_time.to_le_bytes());
+        // 215:200 - temperature sensors 1-8
+        for sensor in self.temp_sensors {
+            buf.extend_from_slice(&sensor.to_le_bytes());
+        }
+        // 219:216 - thermal management temperature 1 transition count
+        buf.extend_from_slice(&self.thermal_management_temp1_transition_count.to_le_bytes());
+        // 223:220 - thermal management temperature 2 transition count
+        buf.extend_from_slice(&self.thermal_management_temp2_transition_count.to_le_bytes());
+        // 511:224 - not tracked
+        buf.resize(start + 512, 0);
+    }
+}
+
+impl ToBytesLE for FwCommitInfo {
+    fn write_le(&self, buf: &mut Vec<u8>) {
+        // 07:00 - old firmware revision
+        write_str(buf, &self.previous_revision, 8, b' ');
+        // 15:08 - new firmware revision
+        write_str(buf, &self.new_revision, 8, b' ');
+        // 16 - firmware commit action
+        // 17 - firmware slot
+        // 18 - status code type for the firmware commit command
+        // 19 - status returned for the firmware commit command
+        buf.extend_from_slice(&[
+            self.commit_action,
+            self.slot_number,
+            self.status_code_type,
+            self.commit_status,
+        ]);
+        // 21:20 - vendor assigned firmware commit result
+        buf.extend_from_slice(&self.commit_result.0.to_le_bytes());
+        // 23:22 - reserved
+        buf.extend_from_slice(&[0, 0]);
+    }
+}
+
+impl ToBytesLE for PorInfo {
+    fn write_le(&self, buf: &mut Vec<u8>) {
+        // 07:00 - firmware revision
+        write_str(buf, &self.firmware_revision, 8, b' ');
+        for reset in &self.resets {
+            reset.write_le(buf);
+        }
+    }
+}
+
+impl ToBytesLE for ControllerResetInfo {
+    fn write_le(&self, buf: &mut Vec<u8>) {
+        // 01:00 - controller id
+        buf.extend_from_slice(&self.ctrl_id.to_le_bytes());
+        // 02 - firmware activation
+        // 03 - operation in progress
+        buf.extend_from_slice(&[self.firmware_activation as u8, self.operation_in_progress]);
+        // 15:04 - reserved
+        buf.resize(buf.len() + 12, 0);
+        // 19:16 - controller power cycle
+        buf.extend_from_slice(&self.power_cycle_count.to_le_bytes());
+        // 27:20 - power on milliseconds
+        buf.extend_from_slice(&self.power_on_ms.to_le_bytes());
+        // 35:28 - controller timestamp
+        self.timestamp.write_le(buf);
+    }
+}
+
+impl ToBytesLE for ThermalExcursionInfo {
+    fn write_le(&self, buf: &mut Vec<u8>) {
+        // 00 - over temperature
+        // 01 - threshold, in degrees Celsius
+        let threshold = self
+            .threshold_kelvin
+            .saturating_sub(KELVIN_OFFSET)
+            .min(u8::MAX as u16);
+        buf.extend_from_slice(&[self.over_threshold as u8, threshold as u8]);
+    }
+}
+
+impl ToBytesLE for FormatNvmCompleteInfo {
+    fn write_le(&self, buf: &mut Vec<u8>) {
+        // 03:00 - namespace id
+        buf.extend_from_slice(&self.nsid.to_le_bytes());
+        // 11:04 - format progress and status, not tracked
+        buf.resize(buf.len() + 8, 0);
+    }
+}
+
+impl ToBytesLE for SetFeatureInfo {
+    fn write_le(&self, buf: &mut Vec<u8>) {
+        // the command dwords after CDW10 come first, the rest is the memory buffer
+        let extra_dwords = self.command_dwords.saturating_sub(1) as usize * 4;
+        let buffer_len = self.data.len().saturating_sub(extra_dwords) as u32;
+        // 03:00 - layout, 31:16 memory buffer count and 02:00 command dword count
+        let layout = buffer_len << 16 | self.command_dwords as u32 & 0x7;
+        buf.extend_from_slice(&layout.to_le_bytes());
+        // 07:04 - CDW10: 07:00 - FID, 31 - SV
+        let cdw10 = (self.save as u32) << 31 | self.feature_id() as u32;
+        buf.extend_from_slice(&cdw10.to_le_bytes());
+        buf.extend_from_slice(&self.data);
+    }
+}
+
+impl ToBytesLE for VendorSpecifcInfo {
+    fn write_le(&self, buf: &mut Vec<u8>) {
+        buf.extend_from_slice(&self.data);
+    }
+}
+
+// The data of these events isn't decoded yet, so it's written back as it was read
+macro_rules! impl_to_bytes_le_undecoded {
+    ($($info:ty,)*) => {
+        $(
+            impl ToBytesLE for $info {
+                fn write_le(&self, buf: &mut Vec<u8>) {
+                    buf.extend_from_slice(&self.data);
+                }
+            }
+        )*
+    };
+}
+
+impl_to_bytes_le_undecoded! {
+    TimestampChangeInfo,
+    NvmHwErrorInfo,
+    ChangeNamespaceInfo,
+    FormatNvmStartInfo,
+    SanitizeStartInfo,
+    SanitizeCompleteInfo,
+    TelementryLogCreatedInfo,
+    TcgDefinedInfo,
+    UnknownInfo,
+}
+
+macro_rules! impl_event_info {
+    ($($info:ty => $event_type:expr,)*) => {
+        $(
+            impl EventInfo for $info {
+                const EVENT_TYPE: u8 = $event_type;
+            }
+        )*
+    };
+}
+
+impl_event_info! {
+    SmartHealthInfo => SMART_HEALTH,
+    FwCommitInfo => FW_COMMIT,
+    TimestampChangeInfo => TIMESTAMP_CHANGE,
+    PorInfo => POR,
+    NvmHwErrorInfo => NVM_HW_ERROR,
+    ChangeNamespaceInfo => CHANGE_NAMESPACE,
+    FormatNvmStartInfo => FORMAT_NVM_START,
+    FormatNvmCompleteInfo => FORMAT_NVM_COMPLETE,
+    SanitizeStartInfo => SANITIZE_START,
+    SanitizeCompleteInfo => SANITIZE_COMPLETE,
+    SetFeatureInfo => SET_FEATURE,
+    TelementryLogCreatedInfo => TELEMENTRY_LOG_CREATED,
+    ThermalExcursionInfo => THERMAL_EXCURSION,
+    VendorSpecifcInfo => VENDOR_SPECIFC,
+    TcgDefinedInfo => TCG_DEFINED,
+}
+
+impl EventInfo for UnknownInfo {
+    // reserved, the type from the header is written instead
+    const EVENT_TYPE: u8 = 0x00;
+
+    fn event_type(&self) -> u8 {
+        self.event_type
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pel::test_helpers::*;
+
+    // parsing fills in the event length, so records compared after a round trip need it too
+    fn sized<T: EventInfo>(record: EventRecord<T>) -> EventRecord<T> {
+        let len = (EVENT_HEADER_LEN + record.info.to_bytes_le().len()) as u16;
+        EventRecord { len, ..record }
+    }
+
+    #[test]
+    fn test_timestamp_round_trip() {
+        for timestamp in [
+            Timestamp::default(),
+            Timestamp {
+                ms: Duration::from_millis(0x554433221100),
+                origin: TimestampOrigin::SetFeature,
+                synch: TimestampSynch::Skipped,
+            },
+            Timestamp {
+                ms: Duration::from_millis(1),
+                origin: TimestampOrigin::Unknown(5),
+                synch: TimestampSynch::Continuous,
+            },
+        ] {
+            let bytes = timestamp.to_bytes_le();
+            assert_eq!(bytes.len(), 8);
+            let (_, parsed) = parse_timestamp(&bytes).unwrap();
+            assert!(parsed.equivalent_to(&timestamp), "{parsed:?} {timestamp:?}");
+        }
+    }
+
+    #[test]
+    fn test_serialize_then_parse() {
+        let mut por = sized(record(
+            3000,
+            PorInfo {
+                firmware_revision: "1.2A".to_string(),
+                resets: vec![ControllerResetInfo {
+                    ctrl_id: 1,
+                    firmware_activation: true,
+                    operation_in_progress: 2,
+                    power_cycle_count: 10,
+                    power_on_ms: 5000,
+                    timestamp: Timestamp {
+                        ms: Duration::from_millis(2500),
+                        ..Default::default()
+                    },
+                }],
+            },
+        ));
+        por.vendor_info = Some(VendorInfo {
+            raw: vec![0xaa, 0xbb, 0xcc, 0xdd],
+            parsed: None,
+        });
+        por.vendor_info_len = 4;
+        por.len += 4;
+
+        let events = vec![
+            Event::SmartHealth(sized(record(
+                1000,
+                SmartHealthInfo {
+                    critical_warning: CriticalWarning(0x04),
+                    composite_temperature: 310,
+                    available_spare: 95,
+                    percentage_used: 7,
+                    data_units_written: 1 << 70,
+                    media_errors: 3,
+                    temp_sensors: [300, 301, 302, 303, 0, 0, 0, 0],
+                    thermal_management_temp2_transition_count: 9,
+                    ..Default::default()
+                },
+            ))),
+            Event::FwCommit(sized(record(
+                2000,
+                FwCommitInfo {
+                    previous_revision: "1.0".to_string(),
+                    new_revision: "2.0".to_string(),
+                    commit_action: 3,
+                    slot_number: 2,
+                    status_code_type: 1,
+                    commit_status: 0x0b,
+                    commit_result: FwCommitResult(0x1234),
+                },
+            ))),
+            Event::Por(por),
+            Event::ThermalExcursion(sized(record(
+                4000,
+                ThermalExcursionInfo {
+                    over_threshold: true,
+                    under_threshold: false,
+                    threshold_kelvin: 353,
+                },
+            ))),
+            Event::SanitizeStart(sized(record(5000, SanitizeStartInfo::default()))),
+        ];
+
+        let mut supp_events = [0u8; 32];
+        for event in &events {
+            let event_type = u8::from(event.event_type().unwrap());
+            supp_events[(event_type / 8) as usize] |= 1 << (event_type % 8);
+        }
+        let mut pel = Pel {
+            num_events: events.len() as u32,
+            revision: 2,
+            header_len: LOG_HEADER_LEN as u16,
+            timestamp: Timestamp {
+                ms: Duration::from_millis(123_456),
+                origin: TimestampOrigin::SetFeature,
+                synch: TimestampSynch::Skipped,
+            },
+            power_on_hours: 1234,
+            power_cycle_count: 56,
+            vid: 0x144d,
+            ssvid: 0x1028,
+            serial_num: "SN12345".to_string(),
+            model_num: "MODEL-01".to_string(),
+            subnqn: "nqn.2014-08.org.nvmexpress:test".to_string(),
+            supp_events: SuppEventsBitmap(supp_events),
+            events: Some(events),
+            generation: Some(7),
+            reporting_context: Some(ReportingContext::MiPort(2)),
+            ..Default::default()
+        };
+        pel.len = pel.to_bytes_le().len() as u64;
+        pel.computed_checksum = Some(pel.compute_log_checksum());
+
+        let bytes = pel.to_bytes_le();
+        let parsed = parse_pel(&bytes).unwrap();
+        assert_eq!(format!("{parsed:?}"), format!("{pel:?}"));
+    }
+
+    #[test]
+    fn test_parse_then_serialize() {
+        let mut fw_commit = [b' '; FW_COMMIT_EVENT_LEN];
+        fw_commit[..3].copy_from_slice(b"1.0");
+        fw_commit[8..11].copy_from_slice(b"2.0");
+        fw_commit[16..24].fill(0);
+        fw_commit[16] = 1;
+        let mut smart = [0u8; SMART_EVENT_LEN];
+        smart[..3].copy_from_slice(&[0x01, 0x36, 0x01]);
+        smart[200..202].copy_from_slice(&310u16.to_le_bytes());
+
+        let mut input = make_full_pel(&[
+            make_event_bytes(SMART_HEALTH, 1, 1, 1000, &smart),
+            make_event_bytes(FW_COMMIT, 1, 1, 2000, &fw_commit),
+            make_event_bytes(THERMAL_EXCURSION, 1, 2, 3000, &[0x01, 80]),
+        ]);
+        // 75:56 - serial number, 115:76 - model number
+        input[56..116].fill(b' ');
+
+        let pel = parse_pel(&input).unwrap();
+        assert_eq!(pel.to_bytes_le(), input);
+    }
+
+    #[test]
+    fn test_hex_string_round_trip() {
+        let mut input = make_full_pel(&[make_smart_event_bytes(1, 1, 1000)]);
+        input[56..63].copy_from_slice(b"SN12345");
+        let pel = parse_pel(&input).unwrap();
+
+        let hex = pel.to_hex_string();
+        assert_eq!(&hex[..2], "0d");
+        let parsed = Pel::from_hex_string(&hex).unwrap();
+        assert_eq!(parsed.serial_num, "SN12345");
+        assert_eq!(parsed.to_bytes_le(), pel.to_bytes_le());
+
+        // xxd -p wraps lines at 60 characters
+        let wrapped: Vec<_> = hex
+            .as_bytes()
+            .chunks(60)
+            .map(|line| std::str::from_utf8(line).unwrap())
+            .collect();
+        let parsed = Pel::from_hex_string(&wrapped.join("\n")).unwrap();
+        assert_eq!(parsed.serial_num, "SN12345");
+
+        assert_eq!(
+            Pel::from_hex_string("0d0").unwrap_err(),
+            PelError::InvalidHexInput
+        );
+        assert_eq!(
+            Pel::from_hex_string("zz").unwrap_err(),
+            PelError::InvalidHexInput
+        );
+    }
+
+    #[test]
+    fn test_nvme_wire_format_round_trip() {
+        let mut pel: Pel = [
+            Event::SmartHealth(record(
+                1000,
+                SmartHealthInfo {
+                    composite_temperature: 310,
+                    percentage_used: 7,
+                    ..Default::default()
+                },
+            )),
+            Event::FormatNvmComplete(record(2000, FormatNvmCompleteInfo { nsid: 1 })),
+            Event::Unknown(EventRecord {
+                // a longer event header
+                header_len: 28,
+                ..record(
+                    3000,
+                    UnknownInfo {
+                        event_type: 0x42,
+                        data: vec![1, 2, 3],
+                    },
+                )
+            }),
+        ]
+        .into_iter()
+        .collect();
+        pel.revision = 2;
+        // the wire format always has a 512 byte header
+        pel.header_len = 600;
+        pel.timestamp = Timestamp {
+            ms: Duration::from_millis(123_456),
+            origin: TimestampOrigin::SetFeature,
+            synch: TimestampSynch::Skipped,
+        };
+        pel.power_on_hours = 1234;
+        pel.power_cycle_count = 56;
+        pel.vid = 0x144d;
+        pel.ssvid = 0x1028;
+        pel.serial_num = "S".repeat(20);
+        pel.model_num = "MODEL-01".to_string();
+        pel.subnqn = "nqn.2014-08.org.nvmexpress:test".to_string();
+        pel.supp_events.0[0] = 1 << SMART_HEALTH;
+        pel.supp_events.0[1] = 1 << (FORMAT_NVM_COMPLETE - 8);
+        pel.generation = Some(7);
+        pel.reporting_context = Some(ReportingContext::NVMPort(3));
+
+        let bytes = pel.serialize_to_nvme_wire_format().unwrap();
+        assert_eq!(bytes[0], PEL_LOG_ID);
+        let parsed = parse_pel(&bytes).unwrap();
+        assert_eq!(parsed.num_events, 3);
+        assert_eq!(parsed.len, bytes.len() as u64);
+        assert_eq!(parsed.revision, pel.revision);
+        assert_eq!(parsed.header_len, 512);
+        assert!(parsed.timestamp.equivalent_to(&pel.timestamp));
+        assert_eq!(parsed.power_on_hours, pel.power_on_hours);
+        assert_eq!(parsed.power_cycle_count, pel.power_cycle_count);
+        assert_eq!(parsed.vid, pel.vid);
+        assert_eq!(parsed.ssvid, pel.ssvid);
+        assert_eq!(parsed.serial_num, pel.serial_num);
+        assert_eq!(parsed.model_num, pel.model_num);
+        assert_eq!(parsed.subnqn, pel.subnqn);
+        assert_eq!(parsed.supp_events.0, pel.supp_events.0);
+        assert_eq!(parsed.generation, pel.generation);
+        assert_eq!(parsed.reporting_context, pel.reporting_context);
+        let events = parsed.events.as_deref().unwrap();
+        assert_eq!(*record_field!(&events[2], header_len), 28);
+        for (parsed, event) in events.iter().zip(pel.events.as_deref().unwrap()) {
+            assert_eq!(parsed.to_bytes_le(), event.to_bytes_le());
+        }
+
+        pel.serial_num.push('1');
+        assert_eq!(
+            pel.serialize_to_nvme_wire_format().unwrap_err(),
+            PelError::FieldTooLong {
+                field: "serial number",
+                len: 21,
+                max: 20
+            }
+        );
+    }
+
+    #[test]
+    fn test_nvme_wire_format_event_too_long() {
+        let pel: Pel = [Event::VendorSpecifc(record(
+            0,
+            VendorSpecifcInfo {
+                vendor_id: 0x144d,
+                data: vec![0; u16::MAX as usize + 1],
+            },
+        ))]
+        .into_iter()
+        .collect();
+        assert_eq!(
+            pel.serialize_to_nvme_wire_format().unwrap_err(),
+            PelError::FieldTooLong {
+                field: "event length",
+                len: u16::MAX as usize + 1,
+                max: u16::MAX as usize
+            }
+        );
+    }
+
+    #[test]
+    fn test_log_checksum() {
+        let input = make_full_pel(&[
+            make_smart_event_bytes(1, 1, 1000),
+            make_fw_commit_event_bytes(1, 1, 2000, "1.0", "2.0"),
+        ]);
+        let mut pel = parse_pel(&input).unwrap();
+        let checksum = pel.computed_checksum.unwrap();
+        assert!(pel.checksum_matches(checksum));
+        // only the events are covered
+        pel.serial_num = "SN12345".to_string();
+        assert!(pel.checksum_matches(checksum));
+
+        let Some(Event::FwCommit(event)) = pel.events.as_mut().unwrap().get_mut(1) else {
+            panic!("expected a firmware commit event");
+        };
+        event.info.slot_number = 2;
+        assert!(!pel.checksum_matches(checksum));
+        assert_ne!(pel.compute_log_checksum(), checksum);
+
+        // bodies aren't parsed, so there's nothing to check them against
+        let pel = parse_pel_headers_only(&input).unwrap();
+        assert_eq!(pel.computed_checksum, None);
+    }
+
+    #[test]
+    fn test_serialize_header_and_events_only() {
+        let input = make_full_pel(&[
+            make_smart_event_bytes(1, 1, 1000),
+            make_event_bytes(THERMAL_EXCURSION, 1, 2, 3000, &[0x01, 80]),
+        ]);
+        let pel = parse_pel(&input).unwrap();
+
+        let header = pel.serialize_header_only();
+        let events = pel.serialize_events_only();
+        let bytes = pel.to_bytes_le();
+        assert_eq!(header.len() + events.len(), bytes.len());
+        assert_eq!([&header[..], &events].concat(), bytes);
+        assert_eq!(events, input[LOG_HEADER_LEN..]);
+
+        assert!(Pel::default().serialize_events_only().is_empty());
+    }
+
+    #[test]
+    fn test_header_counts_written_events() {
+        let mut pel: Pel = [
+            Event::Por(record(1000, PorInfo::default())),
+            Event::ThermalExcursion(record(2000, ThermalExcursionInfo::default())),
+        ]
+        .into_iter()
+        .collect();
+        // stale values from before events were removed
+        pel.num_events = 5;
+        pel.len = 4096;
+
+        let bytes = pel.to_bytes_le();
+        assert_eq!(bytes[4..8], 2u32.to_le_bytes());
+        assert_eq!(bytes[8..16], (bytes.len() as u64).to_le_bytes());
+
+        let bytes = Pel::default().to_bytes_le();
+        assert_eq!(bytes[4..8], 0u32.to_le_bytes());
+        assert_eq!(bytes[8..16], MINIMUM_LOG_SIZE.to_le_bytes());
+    }
+
+    #[test]
+    fn test_undecoded_and_unknown_round_trip() {
+        let mut format_complete = [0u8; 12];
+        format_complete[..4].copy_from_slice(&1u32.to_le_bytes());
+        let mut set_feature = [0u8; 12];
+        // CDW10 and CDW11 logged
+        set_feature[..4].copy_from_slice(&2u32.to_le_bytes());
+        set_feature[4..8].copy_from_slice(&0x55u32.to_le_bytes());
+        set_feature[8..12].copy_from_slice(&7u32.to_le_bytes());
+
+        let mut input = make_full_pel(&[
+            make_event_bytes(SANITIZE_START, 1, 1, 1000, &[1, 2, 3, 4, 5, 6, 7, 8]),
+            make_event_bytes(TELEMENTRY_LOG_CREATED, 1, 1, 2000, &[0xaa; 20]),
+            make_event_bytes(FORMAT_NVM_COMPLETE, 1, 1, 3000, &format_complete),
+            make_event_bytes(SET_FEATURE, 1, 1, 4000, &set_feature),
+            make_event_bytes(VENDOR_SPECIFC, 1, 1, 5000, &[9, 8, 7]),
+            // reserved event type
+            make_event_bytes(0x42, 1, 1, 6000, &[0x11, 0x22, 0x33]),
+        ]);
+        input[56..116].fill(b' ');
+
+        let pel = parse_pel(&input).unwrap();
+        let events = pel.events.as_deref().unwrap();
+        let Event::SanitizeStart(event) = &events[0] else {
+            panic!("expected a sanitize start event, got {:?}", events[0]);
+        };
+        assert_eq!(event.info.data, [1, 2, 3, 4, 5, 6, 7, 8]);
+        let Event::Unknown(event) = &events[5] else {
+            panic!("expected an unknown event, got {:?}", events[5]);
+        };
+        assert_eq!(event.info.event_type, 0x42);
+        assert_eq!(event.info.data, [0x11, 0x22, 0x33]);
+
+        assert_eq!(pel.to_bytes_le(), input);
+    }
+
+    #[test]
+    fn test_set_feature_round_trip() {
+        let mut body = [0u8; 16];
+        // CDW10, CDW11 and a 4 byte memory buffer logged
+        body[..4].copy_from_slice(&(4u32 << 16 | 2).to_le_bytes());
+        body[4..8].copy_from_slice(&(1u32 << 31 | 0x06).to_le_bytes());
+        body[8..12].copy_from_slice(&1u32.to_le_bytes());
+        body[12..16].copy_from_slice(&[0xde, 0xad, 0xbe, 0xef]);
+
+        let mut input = make_full_pel(&[make_event_bytes(SET_FEATURE, 1, 1, 1000, &body)]);
+        input[56..116].fill(b' ');
+
+        let pel = parse_pel(&input).unwrap();
+        let events = pel.events.as_deref().unwrap();
+        let Event::SetFeature(event) = &events[0] else {
+            panic!("expected a set feature event, got {:?}", events[0]);
+        };
+        assert_eq!(event.info.feature, Feature::VolatileWriteCache);
+        assert_eq!(event.info.command_dwords, 2);
+        assert_eq!(event.info.data, [1, 0, 0, 0, 0xde, 0xad, 0xbe, 0xef]);
+
+        assert_eq!(pel.to_bytes_le(), input);
+    }
+}
@@ -0,0 +1,37 @@
+use std::collections::BTreeMap;
+
+use super::{EventType, Pel};
+
+// Summary counts of a log, which don't borrow from it
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct PelStats {
+    pub num_events: usize,
+    pub events_by_type: BTreeMap<EventType, usize>,
+    pub unknown_events: usize,
+    pub first_event_ms: Option<u64>,
+    pub last_event_ms: Option<u64>,
+}
+
+impl From<&Pel> for PelStats {
+    fn from(pel: &Pel) -> Self {
+        let mut stats = PelStats::default();
+        for event in pel.events.iter().flatten() {
+            stats.num_events += 1;
+            // headers only parses return every event as unknown, but still know the type
+            match EventType::try_from(event.type_code()) {
+                Ok(event_type) => *stats.events_by_type.entry(event_type).or_default() += 1,
+                Err(_) => stats.unknown_events += 1,
+            }
+            let ms = event.timestamp().millis();
+            stats.first_event_ms = Some(stats.first_event_ms.map_or(ms, |first| first.min(ms)));
+            stats.last_event_ms = Some(stats.last_event_ms.map_or(ms, |last| last.max(ms)));
+        }
+        stats
+    }
+}
+
+impl From<Pel> for PelStats {
+    fn from(pel: Pel) -> Self {
+        PelStats::from(&pel)
+    }
+}
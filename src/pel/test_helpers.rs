@@ -0,0 +1,142 @@
+// Builders for synthetic binary logs shared by the parser tests
+
+use super::*;
+
+pub(crate) const LOG_HEADER_LEN: usize = 512;
+pub(crate) use super::EVENT_HEADER_LEN;
+pub(crate) const SMART_EVENT_LEN: usize = 512;
+pub(crate) const FW_COMMIT_EVENT_LEN: usize = 24;
+
+pub(crate) fn make_minimal_log_header() -> Vec<u8> {
+    let mut header = vec![0u8; LOG_HEADER_LEN];
+    // 00 - log id
+    header[0] = 0x0d;
+    // 15:08 - total log length
+    header[8..16].copy_from_slice(&(LOG_HEADER_LEN as u64).to_le_bytes());
+    // 16 - log revision
+    header[16] = 1;
+    // 19:18 - log header length
+    header[18..20].copy_from_slice(&(LOG_HEADER_LEN as u16).to_le_bytes());
+    header
+}
+
+pub(crate) fn make_event_bytes(
+    event_type: u8,
+    revision: u8,
+    ctrl_id: u16,
+    ts_ms: u64,
+    body: &[u8],
+) -> Vec<u8> {
+    let mut event = vec![0u8; EVENT_HEADER_LEN];
+    event[0] = event_type;
+    event[1] = revision;
+    // EHL excludes the first 3 bytes
+    event[2] = (EVENT_HEADER_LEN - 3) as u8;
+    event[4..6].copy_from_slice(&ctrl_id.to_le_bytes());
+    event[6..12].copy_from_slice(&ts_ms.to_le_bytes()[..6]);
+    event[22..24].copy_from_slice(&(body.len() as u16).to_le_bytes());
+    event.extend_from_slice(body);
+    event
+}
+
+pub(crate) fn make_smart_event_bytes(revision: u8, ctrl_id: u16, ts_ms: u64) -> Vec<u8> {
+    make_event_bytes(
+        SMART_HEALTH,
+        revision,
+        ctrl_id,
+        ts_ms,
+        &[0u8; SMART_EVENT_LEN],
+    )
+}
+
+pub(crate) fn make_fw_commit_event_bytes(
+    revision: u8,
+    ctrl_id: u16,
+    ts_ms: u64,
+    old_fw: &str,
+    new_fw: &str,
+) -> Vec<u8> {
+    let mut body = [0u8; FW_COMMIT_EVENT_LEN];
+    // 07:00 - old firmware revision
+    body[..old_fw.len()].copy_from_slice(old_fw.as_bytes());
+    // 15:08 - new firmware revision
+    body[8..8 + new_fw.len()].copy_from_slice(new_fw.as_bytes());
+    make_event_bytes(FW_COMMIT, revision, ctrl_id, ts_ms, &body)
+}
+
+pub(crate) fn make_full_pel(events: &[Vec<u8>]) -> Vec<u8> {
+    let mut pel = make_minimal_log_header();
+    let len = pel.len() + events.iter().map(Vec::len).sum::<usize>();
+    // 07:04 - total number of events
+    pel[4..8].copy_from_slice(&(events.len() as u32).to_le_bytes());
+    // 15:08 - total log length
+    pel[8..16].copy_from_slice(&(len as u64).to_le_bytes());
+    // 511:480 - supported events bitmap
+    for event in events {
+        pel[480 + (event[0] / 8) as usize] |= 1 << (event[0] % 8);
+    }
+    for event in events {
+        pel.extend_from_slice(event);
+    }
+    pel
+}
+
+// An event record with only the timestamp set, for tests that build a `Pel` in memory
+pub(crate) fn record<T>(ms: u64, info: T) -> EventRecord<T> {
+    EventRecord {
+        revision: 1,
+        header_len: EVENT_HEADER_LEN as u8,
+        ctrl_id: 0,
+        timestamp: Timestamp {
+            ms: Duration::from_millis(ms),
+            ..Default::default()
+        },
+        vendor_info_len: 0,
+        len: EVENT_HEADER_LEN as u16,
+        vendor_info: None,
+        info: Box::new(info),
+    }
+}
+
+pub(crate) fn event_at(ms: u64) -> Event {
+    Event::Unknown(record(ms, UnknownInfo::default()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pel::parser::parse_event_header;
+
+    #[test]
+    fn test_make_event_bytes() {
+        let event = make_smart_event_bytes(1, 2, 3);
+        assert_eq!(event.len(), EVENT_HEADER_LEN + SMART_EVENT_LEN);
+        let (remainder, (event_type, record)) = parse_event_header(&event).unwrap();
+        assert_eq!(remainder.len(), SMART_EVENT_LEN);
+        assert_eq!(event_type, SMART_HEALTH);
+        assert_eq!(record.len as usize, event.len());
+
+        let event = make_fw_commit_event_bytes(1, 2, 3, "1.0.0", "1.5.0");
+        assert_eq!(event.len(), EVENT_HEADER_LEN + FW_COMMIT_EVENT_LEN);
+        let (remainder, (event_type, record)) = parse_event_header(&event).unwrap();
+        assert_eq!(remainder.len(), FW_COMMIT_EVENT_LEN);
+        assert_eq!(event_type, FW_COMMIT);
+        assert_eq!(record.len as usize, event.len());
+    }
+
+    #[test]
+    fn test_make_full_pel() {
+        let events = [
+            make_smart_event_bytes(1, 0, 0),
+            make_fw_commit_event_bytes(1, 0, 10, "1.0.0", "1.5.0"),
+        ];
+        let bytes = make_full_pel(&events);
+        let (remainder, pel) = parse_log_header(&bytes).unwrap();
+        assert_eq!(remainder.len(), events[0].len() + events[1].len());
+        assert_eq!(pel.num_events, 2);
+        assert_eq!(pel.len as usize, bytes.len());
+        assert!(pel.supp_events.is_supported(SMART_HEALTH));
+        assert!(pel.supp_events.is_supported(FW_COMMIT));
+        assert!(!pel.supp_events.is_supported(POR));
+    }
+}
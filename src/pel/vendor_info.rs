@@ -0,0 +1,172 @@
+use std::{any::Any, collections::HashMap, fmt};
+
+use super::PelError;
+
+/// Decodes the vendor specific information (VSI) that can precede any event's data.
+pub trait VendorInfoParser: Send + Sync {
+    fn parse(
+        &self,
+        vid: u16,
+        ssvid: u16,
+        event_type: u8,
+        revision: u8,
+        data: &[u8],
+    ) -> Result<Box<dyn Any + Send + Sync>, PelError>;
+}
+
+/// Vendor info parsers keyed by the PCI vendor id and subsystem vendor id of the log.
+#[derive(Default)]
+pub struct VendorRegistry {
+    parsers: HashMap<(u16, u16), Box<dyn VendorInfoParser>>,
+}
+
+impl VendorRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn register(&mut self, vid: u16, ssvid: u16, parser: impl VendorInfoParser + 'static) {
+        self.parsers.insert((vid, ssvid), Box::new(parser));
+    }
+
+    pub fn get(&self, vid: u16, ssvid: u16) -> Option<&dyn VendorInfoParser> {
+        self.parsers.get(&(vid, ssvid)).map(Box::as_ref)
+    }
+}
+
+impl fmt::Debug for VendorRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.parsers.keys()).finish()
+    }
+}
+
+// The parser for one log, already looked up from the registry
+#[derive(Clone, Copy)]
+pub(crate) struct VendorContext<'a> {
+    pub parser: &'a dyn VendorInfoParser,
+    pub vid: u16,
+    pub ssvid: u16,
+}
+
+/// Vendor specific information attached to an event. `parsed` is only set when a
+/// [`VendorInfoParser`] was registered for the device.
+#[derive(Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VendorInfo {
+    pub raw: Vec<u8>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub parsed: Option<Box<dyn Any + Send + Sync>>,
+}
+
+impl fmt::Debug for VendorInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("VendorInfo")
+            .field("raw", &self.raw)
+            .field("parsed", &self.parsed.is_some())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+    use crate::pel::test_helpers::*;
+    use crate::pel::*;
+
+    // vid, ssvid, event type, revision and data of each call
+    type Calls = Arc<Mutex<Vec<(u16, u16, u8, u8, Vec<u8>)>>>;
+
+    // Records every call and returns the data length
+    struct DummyParser {
+        calls: Calls,
+    }
+
+    impl VendorInfoParser for DummyParser {
+        fn parse(
+            &self,
+            vid: u16,
+            ssvid: u16,
+            event_type: u8,
+            revision: u8,
+            data: &[u8],
+        ) -> Result<Box<dyn Any + Send + Sync>, PelError> {
+            self.calls
+                .lock()
+                .unwrap()
+                .push((vid, ssvid, event_type, revision, data.to_vec()));
+            Ok(Box::new(data.len()))
+        }
+    }
+
+    fn make_log() -> Vec<u8> {
+        let mut body = vec![0xaa, 0xbb, 0xcc, 0xdd];
+        body.extend_from_slice(&[0u8; 16]);
+        let mut event = make_event_bytes(POR, 2, 1, 1000, &body);
+        // 21:20 - vendor specific information length
+        event[20..22].copy_from_slice(&4u16.to_le_bytes());
+
+        let mut log = make_full_pel(&[event, make_smart_event_bytes(1, 1, 2000)]);
+        // 53:52 - pci vendor id, 55:54 - pci subsystem vendor id
+        log[52..54].copy_from_slice(&0x144du16.to_le_bytes());
+        log[54..56].copy_from_slice(&0x1028u16.to_le_bytes());
+        log
+    }
+
+    #[test]
+    fn test_parse_pel_with_vendor() {
+        let calls = Calls::default();
+        let mut registry = VendorRegistry::new();
+        registry.register(
+            0x144d,
+            0x1028,
+            DummyParser {
+                calls: calls.clone(),
+            },
+        );
+
+        let pel = parse_pel_with_vendor(&make_log(), &registry).unwrap();
+        assert_eq!(
+            *calls.lock().unwrap(),
+            [(0x144d, 0x1028, POR, 2, vec![0xaa, 0xbb, 0xcc, 0xdd])]
+        );
+
+        let Event::Por(por) = &pel[0] else {
+            panic!("expected a POR event, got {:?}", pel[0]);
+        };
+        let vendor_info = por.vendor_info.as_ref().unwrap();
+        assert_eq!(vendor_info.raw, [0xaa, 0xbb, 0xcc, 0xdd]);
+        let parsed = vendor_info.parsed.as_ref().unwrap();
+        assert_eq!(parsed.downcast_ref::<usize>(), Some(&4));
+
+        // events without vendor info are left alone
+        let Event::SmartHealth(smart) = &pel[1] else {
+            panic!("expected a SMART event, got {:?}", pel[1]);
+        };
+        assert!(smart.vendor_info.is_none());
+    }
+
+    #[test]
+    fn test_parse_pel_without_vendor() {
+        let calls = Calls::default();
+        let mut registry = VendorRegistry::new();
+        registry.register(
+            0x144d,
+            0x0000,
+            DummyParser {
+                calls: calls.clone(),
+            },
+        );
+
+        for pel in [
+            parse_pel(&make_log()).unwrap(),
+            parse_pel_with_vendor(&make_log(), &registry).unwrap(),
+        ] {
+            let vendor_info = record_field!(&pel[0], vendor_info).as_ref().unwrap();
+            assert_eq!(vendor_info.raw, [0xaa, 0xbb, 0xcc, 0xdd]);
+            assert!(vendor_info.parsed.is_none());
+        }
+        assert!(calls.lock().unwrap().is_empty());
+    }
+}
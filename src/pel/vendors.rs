@@ -0,0 +1,43 @@
+// PCI vendor IDs of common NVMe SSD vendors, checked against the PCI ID repository
+// (https://pci-ids.ucw.cz). Phison is 0x1987 there, 0x1d97 belongs to Shenzhen Longsys
+pub const PCI_VENDORS: &[(u16, &str)] = &[
+    (0x1028, "Dell"),
+    (0x1179, "Toshiba"),
+    (0x126f, "Silicon Motion"),
+    (0x1344, "Micron"),
+    (0x144d, "Samsung"),
+    (0x15b7, "Western Digital"),
+    (0x1987, "Phison"),
+    (0x1b4b, "Marvell"),
+    (0x1bb1, "Seagate"),
+    (0x1c58, "HGST"),
+    (0x1c5c, "SK Hynix"),
+    (0x1cc1, "ADATA"),
+    (0x1d97, "Longsys"),
+    (0x1e0f, "KIOXIA"),
+    (0x1e4b, "Maxio"),
+    (0x2646, "Kingston"),
+    (0x8086, "Intel"),
+];
+
+// PCI subsystem vendor IDs of major server OEMs
+pub const PCI_SUBSYSTEM_VENDORS: &[(u16, &str)] = &[
+    (0x1014, "IBM"),
+    (0x1028, "Dell"),
+    (0x103c, "HP"),
+    (0x108e, "Oracle"),
+    (0x1137, "Cisco"),
+    (0x1590, "HPE"),
+    (0x15d9, "Supermicro"),
+    (0x1734, "Fujitsu"),
+    (0x17aa, "Lenovo"),
+    (0x19e5, "Huawei"),
+    (0x1bd4, "Inspur"),
+];
+
+pub fn lookup(table: &[(u16, &'static str)], id: u16) -> Option<&'static str> {
+    table
+        .iter()
+        .find(|(vendor_id, _)| *vendor_id == id)
+        .map(|(_, name)| *name)
+}
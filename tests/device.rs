@@ -0,0 +1,15 @@
+// Needs a real NVMe device and permission to send it admin commands:
+// NVME_PEL_DEVICE=/dev/nvme0 cargo test --features linux-ioctl -- --ignored
+#![cfg(all(feature = "linux-ioctl", target_os = "linux"))]
+
+use nvme_pel::device::PelReader;
+
+#[test]
+#[ignore]
+fn test_read_pel() {
+    let path = std::env::var("NVME_PEL_DEVICE").unwrap_or_else(|_| "/dev/nvme0".to_string());
+    let pel = PelReader::open(&path).unwrap().read_pel().unwrap();
+
+    assert!(pel.validate().is_ok());
+    println!("{pel}");
+}